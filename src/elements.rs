@@ -0,0 +1,121 @@
+use std::ops::Range;
+
+use bit_vec::BitVec;
+
+/// Bars and spaces of a linear symbol with their widths, grouped by character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elements {
+    /// Bars and spaces as `(is_bar, width)`, widths in units of [`Elements::scale`]
+    pub elements: Vec<(bool, usize)>,
+    /// Range of [`Elements::elements`] taken by each character, start and stop patterns
+    /// included, empty when the elements are not grouped by character
+    pub characters: Vec<Range<usize>>,
+    /// Units of the widths in a module, more than one for wide to narrow ratios or gaps which
+    /// are not whole numbers of modules
+    pub scale: usize,
+}
+
+impl Default for Elements {
+    fn default() -> Self {
+        Self {
+            elements: Vec::new(),
+            characters: Vec::new(),
+            scale: 1,
+        }
+    }
+}
+
+impl Elements {
+    /// Append the elements of a character, starting with a bar
+    pub(crate) fn push_character(&mut self, widths: impl IntoIterator<Item = usize>) {
+        let start = self.elements.len();
+        self.elements.extend(
+            widths
+                .into_iter()
+                .enumerate()
+                .map(|(i, width)| (i % 2 == 0, width)),
+        );
+        self.characters.push(start..self.elements.len());
+    }
+
+    /// Run lengths of modules cut into characters of the given numbers of modules, each
+    /// character starting with a new element
+    pub(crate) fn grouped(modules: &BitVec, lengths: &[usize]) -> Self {
+        let mut elements = Self::default();
+        let mut modules = modules.iter();
+        for &len in lengths {
+            let start = elements.elements.len();
+            let character = Self::from_iter(modules.by_ref().take(len));
+            elements.elements.extend(character.elements);
+            elements.characters.push(start..elements.elements.len());
+        }
+        elements
+    }
+
+    /// Append the elements of the following part of the symbol, its characters after these
+    pub(crate) fn append(&mut self, other: Elements) {
+        let offset = self.elements.len();
+        self.characters.extend(
+            other
+                .characters
+                .into_iter()
+                .map(|range| range.start + offset..range.end + offset),
+        );
+        self.elements.extend(other.elements);
+    }
+
+    /// Range of the units taken by each character, in units of [`Elements::scale`]
+    pub(crate) fn character_units(&self) -> Vec<Range<usize>> {
+        let mut starts = vec![0];
+        starts.extend(self.elements.iter().scan(0, |end, &(_, width)| {
+            *end += width;
+            Some(*end)
+        }));
+        self.characters
+            .iter()
+            .map(|range| starts[range.start]..starts[range.end])
+            .collect()
+    }
+
+    /// Elements of a character
+    pub fn character(&self, i: usize) -> &[(bool, usize)] {
+        &self.elements[self.characters[i].clone()]
+    }
+
+    /// Width of the elements, in units of [`Elements::scale`]
+    pub fn width(&self) -> usize {
+        self.elements.iter().map(|&(_, width)| width).sum()
+    }
+
+    /// Units of the elements, `true` for bars, a module taking [`Elements::scale`] of them
+    pub fn modules(&self) -> BitVec {
+        let mut modules = BitVec::with_capacity(self.width());
+        for &(is_bar, width) in &self.elements {
+            modules.grow(width, is_bar);
+        }
+        modules
+    }
+}
+
+/// Run lengths of modules, `true` for bars, not grouped by character
+impl FromIterator<bool> for Elements {
+    fn from_iter<T: IntoIterator<Item = bool>>(modules: T) -> Self {
+        let mut elements: Vec<(bool, usize)> = Vec::new();
+        for is_bar in modules {
+            match elements.last_mut() {
+                Some((last, width)) if *last == is_bar => *width += 1,
+                _ => elements.push((is_bar, 1)),
+            }
+        }
+        Self {
+            elements,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&BitVec> for Elements {
+    fn from(modules: &BitVec) -> Self {
+        modules.iter().collect()
+    }
+}
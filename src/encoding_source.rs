@@ -1,49 +1,51 @@
-use bit_vec::BitVec;
-
-use crate::{encode, BarcodeFormat, EncodingError};
+use crate::{encode, BarcodeFormat, EncodingError, Symbol};
 
+/// Encode borrowed data, see [`encode`]
 pub trait BorrowEncodingSource {
-    fn barcode_encode(&self, format: BarcodeFormat) -> Result<BitVec, EncodingError>;
+    /// Encode `self` with the selected format
+    fn barcode_encode(&self, format: BarcodeFormat) -> Result<Symbol, EncodingError>;
 }
 
+/// Encode owned data, see [`encode`]
 pub trait OwnedEncodingSource {
-    fn barcode_encode_owned(self, format: BarcodeFormat) -> Result<BitVec, EncodingError>;
+    /// Encode `self` with the selected format
+    fn barcode_encode_owned(self, format: BarcodeFormat) -> Result<Symbol, EncodingError>;
 }
 
 impl BorrowEncodingSource for String {
-    fn barcode_encode(&self, format: BarcodeFormat) -> Result<BitVec, EncodingError> {
+    fn barcode_encode(&self, format: BarcodeFormat) -> Result<Symbol, EncodingError> {
         let data = self.chars().collect::<Vec<_>>();
         encode(&data, format)
     }
 }
 
 impl OwnedEncodingSource for String {
-    fn barcode_encode_owned(mut self, format: BarcodeFormat) -> Result<BitVec, EncodingError> {
+    fn barcode_encode_owned(mut self, format: BarcodeFormat) -> Result<Symbol, EncodingError> {
         let data = self.drain(0..self.len()).collect::<Vec<_>>();
         encode(&data, format)
     }
 }
 
 impl BorrowEncodingSource for [char] {
-    fn barcode_encode(&self, format: BarcodeFormat) -> Result<BitVec, EncodingError> {
+    fn barcode_encode(&self, format: BarcodeFormat) -> Result<Symbol, EncodingError> {
         encode(self, format)
     }
 }
 
 impl OwnedEncodingSource for &[char] {
-    fn barcode_encode_owned(self, format: BarcodeFormat) -> Result<BitVec, EncodingError> {
+    fn barcode_encode_owned(self, format: BarcodeFormat) -> Result<Symbol, EncodingError> {
         encode(self, format)
     }
 }
 
 impl OwnedEncodingSource for &str {
-    fn barcode_encode_owned(self, format: BarcodeFormat) -> Result<BitVec, EncodingError> {
+    fn barcode_encode_owned(self, format: BarcodeFormat) -> Result<Symbol, EncodingError> {
         self.to_string().barcode_encode_owned(format)
     }
 }
 
 impl BorrowEncodingSource for &str {
-    fn barcode_encode(&self, format: BarcodeFormat) -> Result<BitVec, EncodingError> {
+    fn barcode_encode(&self, format: BarcodeFormat) -> Result<Symbol, EncodingError> {
         self.to_string().barcode_encode(format)
     }
 }
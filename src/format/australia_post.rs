@@ -0,0 +1,111 @@
+use super::GaloisField;
+use crate::{EncodingError, FourStateBar, Symbol};
+
+/// Australia Post 4-state customer barcode format
+pub struct AustraliaPost;
+
+/// Barcode of an [`AustraliaPost`] symbol, fixing its format control code and the customer
+/// information following the 8 digit delivery point identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AustraliaPostFormat {
+    /// Standard Customer Barcode, the sorting code alone
+    StandardCustomer,
+    /// Customer Barcode 2, with up to 5 characters or 8 digits of customer information
+    Customer2,
+    /// Customer Barcode 3, with up to 10 characters or 15 digits of customer information
+    Customer3,
+    /// Reply Paid Barcode, the sorting code of up to 8 digits alone
+    ReplyPaid,
+    /// Routing Barcode, the sorting code of up to 8 digits alone
+    Routing,
+    /// Redirection Barcode, the sorting code of up to 8 digits alone
+    Redirection,
+}
+
+/// Characters of the customer information encoded with the C table
+const C_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz #";
+
+/// Three bars of each character of [`C_CHARS`], two bits per bar
+const C_TABLE: [u8; 64] = [
+    42, 48, 49, 50, 52, 53, 54, 56, 57, 58, 0, 1, 2, 4, 5, 6, 8, 9, 10, 16, 17, 18, 20, 21, 22, 24,
+    25, 26, 32, 33, 34, 36, 37, 38, 40, 41, 11, 12, 13, 14, 15, 19, 23, 27, 28, 29, 30, 31, 35, 39,
+    43, 44, 45, 46, 47, 51, 55, 59, 60, 61, 62, 63, 3, 7,
+];
+
+/// Bars by their 2 bit value
+const BARS: [FourStateBar; 4] = [
+    FourStateBar::Full,
+    FourStateBar::Ascender,
+    FourStateBar::Descender,
+    FourStateBar::Tracker,
+];
+
+impl AustraliaPost {
+    /// Encode the delivery point identifier and the customer information as
+    /// [`Symbol::FourState`] bars.
+    ///
+    /// Customer barcodes take the 8 digit identifier followed by their customer information,
+    /// digits only being encoded more densely when there are more of them than characters fit,
+    /// and shorter information being padded with filler bars, while the other barcodes are
+    /// padded with leading zeros.
+    pub fn encode(format: AustraliaPostFormat, data: &[char]) -> Result<Symbol, EncodingError> {
+        use AustraliaPostFormat::*;
+
+        let numeric = data.iter().all(char::is_ascii_digit);
+        // format control code, whether the customer information takes the N table, and the
+        // number of bars before the Reed-Solomon parity
+        let (fcc, n_table, len) = match (format, data.len()) {
+            (StandardCustomer, 8) => (11, false, 21),
+            (Customer2, 8..=13) => (59, false, 36),
+            (Customer2, 14..=16) if numeric => (59, true, 36),
+            (Customer3, 8..=18) => (62, false, 51),
+            (Customer3, 19..=23) if numeric => (62, true, 51),
+            (ReplyPaid, ..=8) => (45, false, 21),
+            (Routing, ..=8) => (87, false, 21),
+            (Redirection, ..=8) => (92, false, 21),
+            (Customer2, 14..=16) | (Customer3, 19..=23) => return Err(EncodingError::WrongChar),
+            _ => return Err(EncodingError::WrongSize),
+        };
+        let mut data = data.to_vec();
+        if data.len() < 8 {
+            data.splice(0..0, std::iter::repeat_n('0', 8 - data.len()));
+        }
+        if !data[..8].iter().all(char::is_ascii_digit) {
+            return Err(EncodingError::WrongChar);
+        }
+
+        // 2 bit bar values, the digits taking two bars and the other characters three
+        let mut values = Vec::new();
+        let push_digit = |values: &mut Vec<u8>, digit: u32| {
+            values.extend([(digit / 3) as u8, (digit % 3) as u8]);
+        };
+        for digit in [fcc / 10, fcc % 10] {
+            push_digit(&mut values, digit);
+        }
+        for (i, c) in data.iter().enumerate() {
+            match c.to_digit(10) {
+                Some(digit) if i < 8 || n_table => push_digit(&mut values, digit),
+                _ => {
+                    let index = C_CHARS.find(*c).ok_or(EncodingError::WrongChar)?;
+                    values.extend((0..3).rev().map(|i| C_TABLE[index] >> (2 * i) & 3));
+                }
+            }
+        }
+        // filler bars up to the end of the customer information
+        values.resize(len, 3);
+
+        let triples = values
+            .chunks(3)
+            .map(|triple| triple.iter().fold(0, |acc, v| acc << 2 | v))
+            .collect::<Vec<u8>>();
+        let parity = GaloisField::new(6, 0x43).encode(&triples, 4, 1);
+        for symbol in parity {
+            values.extend((0..3).rev().map(|i| symbol >> (2 * i) & 3));
+        }
+
+        let mut bars = vec![FourStateBar::Ascender, FourStateBar::Tracker];
+        bars.extend(values.into_iter().map(|v| BARS[v as usize]));
+        bars.extend([FourStateBar::Ascender, FourStateBar::Tracker]);
+        Ok(Symbol::FourState(bars))
+    }
+}
@@ -0,0 +1,142 @@
+use bit_vec::BitVec;
+
+use crate::{EncodingError, Symbol};
+
+/// Channel Code format, a value of up to 7 digits in 3 to 8 channels of a bar and a space
+/// following a finder pattern
+pub struct ChannelCode;
+
+/// Largest value held by 3 to 8 channels
+const MAX_VALUES: [u32; 6] = [26, 292, 3493, 44072, 576688, 7742862];
+
+/// Narrow bars and spaces of the finder pattern
+const FINDER_LEN: usize = 9;
+
+/// Narrow elements in a row after which a narrow bar is not allowed
+const MAX_NARROW_RUN: usize = 4;
+
+impl ChannelCode {
+    /// Encode up to 7 digits as a [`Symbol::Linear`], in the fewest channels holding their
+    /// value, with at least one channel more than the number of digits so that leading zeros
+    /// are kept
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        match data.len() {
+            0 => return Err(EncodingError::WrongSize),
+            8.. => return Err(EncodingError::TooLong),
+            _ => {}
+        }
+        let value = data
+            .iter()
+            .try_fold(0, |acc, c| Some(acc * 10 + c.to_digit(10)?))
+            .ok_or(EncodingError::WrongChar)?;
+        let channels = (3..=8)
+            .find(|&channels| channels > data.len() && value <= MAX_VALUES[channels - 3])
+            .unwrap_or(8);
+        Self::encode_channels(channels, value)
+    }
+
+    /// Encode a value in 3 to 8 channels as a [`Symbol::Linear`]
+    pub fn encode_channels(channels: usize, value: u32) -> Result<Symbol, EncodingError> {
+        if !(3..=8).contains(&channels) {
+            return Err(EncodingError::WrongSize);
+        }
+        if value > MAX_VALUES[channels - 3] {
+            return Err(EncodingError::TooLong);
+        }
+
+        let mut modules = BitVec::from_elem(FINDER_LEN, false);
+        for i in (0..FINDER_LEN).step_by(2) {
+            modules.set(i, true);
+        }
+        let mut counter = Counter::new(channels);
+        let (mut spaces, mut bars) = (2 * channels - 1, 2 * channels - 1);
+        let mut narrow_run = MAX_NARROW_RUN;
+        let mut rank = value as u64;
+
+        // the patterns of each channel in increasing order of space then bar width, skipping
+        // the patterns completed in fewer ways than the rank left
+        for channel in 0..channels {
+            let widest = |left: usize| left - (channels - channel - 1);
+            let (space, bar) = (1..=widest(spaces))
+                .flat_map(|space| (1..=widest(bars)).map(move |bar| (space, bar)))
+                .find(|&(space, bar)| {
+                    let Some(run) = next_run(narrow_run, space, bar) else {
+                        return false;
+                    };
+                    let count = counter.count(channel + 1, spaces - space, bars - bar, run);
+                    if rank < count {
+                        return true;
+                    }
+                    rank -= count;
+                    false
+                })
+                .ok_or(EncodingError::TooLong)?;
+
+            modules.grow(space, false);
+            modules.grow(bar, true);
+            spaces -= space;
+            bars -= bar;
+            narrow_run = next_run(narrow_run, space, bar).unwrap_or(0);
+        }
+        Ok(Symbol::Linear(modules))
+    }
+}
+
+/// Narrow elements in a row after a space and a bar, [`None`] if the bar is not allowed
+fn next_run(run: usize, space: usize, bar: usize) -> Option<usize> {
+    let run = if space == 1 {
+        (run + 1).min(MAX_NARROW_RUN)
+    } else {
+        0
+    };
+    match bar {
+        1 if run == MAX_NARROW_RUN => None,
+        1 => Some(run + 1),
+        _ => Some(0),
+    }
+}
+
+/// Memoized number of ways to complete the channels
+struct Counter {
+    channels: usize,
+    counts: Vec<Option<u64>>,
+}
+
+impl Counter {
+    fn new(channels: usize) -> Self {
+        let width = 2 * channels;
+        Self {
+            channels,
+            counts: vec![None; (channels + 1) * width * width * (MAX_NARROW_RUN + 1)],
+        }
+    }
+
+    /// Ways to complete the channels from `channel` on with the given widths left, after a
+    /// run of narrow elements
+    fn count(&mut self, channel: usize, spaces: usize, bars: usize, run: usize) -> u64 {
+        let left = self.channels - channel;
+        if left == 0 {
+            return (spaces == 0 && bars == 0) as u64;
+        }
+        if spaces < left || bars < left {
+            return 0;
+        }
+        let width = 2 * self.channels;
+        let index = ((channel * width + spaces) * width + bars) * (MAX_NARROW_RUN + 1)
+            + run.min(MAX_NARROW_RUN);
+        if let Some(count) = self.counts[index] {
+            return count;
+        }
+
+        let mut count = 0;
+        for space in 1..=spaces - (left - 1) {
+            for bar in 1..=bars - (left - 1) {
+                if let Some(run) = next_run(run, space, bar) {
+                    count += self.count(channel + 1, spaces - space, bars - bar, run);
+                }
+            }
+        }
+        self.counts[index] = Some(count);
+        count
+    }
+}
@@ -1,33 +1,90 @@
 use bit_vec::BitVec;
 
-use crate::EncodingError;
+use crate::{Elements, EncodingError};
 
-use super::DynamicBarcodeEncoding;
+use super::{DynamicBarcodeEncoding, ElementWidths};
 
 /// Code 11 format, for more [`info`](https://web.archive.org/web/20070202060711/http://www.barcodeisland.com/code11.phtml)
 pub struct Code11;
 
-impl DynamicBarcodeEncoding for Code11 {
-    fn encode(chars: &[char]) -> Result<BitVec, EncodingError> {
-        let mut ret = BitVec::new();
-        ret.extend([true, false, true, true, false, false, true, false]);
+/// Wide elements of the characters, bars and spaces alternating from the first bar
+const CODE_11_CHARS: [(char, [bool; 5]); 11] = [
+    ('0', [false, false, false, false, true]),
+    ('1', [true, false, false, false, true]),
+    ('2', [false, true, false, false, true]),
+    ('3', [true, true, false, false, false]),
+    ('4', [false, false, true, false, true]),
+    ('5', [true, false, true, false, false]),
+    ('6', [false, true, true, false, false]),
+    ('7', [false, false, false, true, true]),
+    ('8', [true, false, false, true, false]),
+    ('9', [true, false, false, false, false]),
+    ('-', [false, false, true, false, false]),
+];
+
+/// Wide elements of the start and stop character
+const START_STOP: [bool; 5] = [false, false, true, true, false];
+
+impl Code11 {
+    /// Wide elements of 2 modules and gaps of 1 module
+    pub const WIDTHS: ElementWidths = ElementWidths::whole(2);
+
+    /// Encode the provided chars as bars and spaces grouped by character, the start and stop
+    /// characters included, with the given element widths.
+    ///
+    /// Characters other than digits and `-` are skipped.
+    pub fn elements(chars: &[char], widths: ElementWidths) -> Result<Elements, EncodingError> {
+        let (narrow, wide, gap) = widths.units()?;
+        let widths = |pattern: [bool; 5], with_gap: bool| {
+            pattern
+                .map(|is_wide| if is_wide { wide } else { narrow })
+                .into_iter()
+                .chain(with_gap.then_some(gap))
+        };
+
+        let mut elements = Elements {
+            scale: narrow,
+            ..Default::default()
+        };
+        elements.push_character(widths(START_STOP, true));
         for c in chars {
-            match c {
-                '0' => ret.extend([true, false, true, false, true, true, false]),
-                '1' => ret.extend([true, true, false, true, false, true, true, false]),
-                '2' => ret.extend([true, false, false, true, false, true, true, false]),
-                '3' => ret.extend([true, true, false, false, true, false, true, false]),
-                '4' => ret.extend([true, false, true, true, false, true, true, false]),
-                '5' => ret.extend([true, true, false, true, true, false, true, false]),
-                '6' => ret.extend([true, false, false, true, true, false, true, false]),
-                '7' => ret.extend([true, false, true, false, false, true, true, false]),
-                '8' => ret.extend([true, true, false, true, false, false, true, false]),
-                '9' => ret.extend([true, true, false, true, false, true, false]),
-                '-' => ret.extend([true, false, true, true, false, true, false]),
-                _ => continue,
-            };
+            if let Some((_, pattern)) = CODE_11_CHARS.iter().find(|(char, _)| char == c) {
+                elements.push_character(widths(*pattern, true));
+            }
         }
-        ret.extend([true, false, true, true, false, false, true]);
-        Ok(ret)
+        elements.push_character(widths(START_STOP, false));
+        Ok(elements)
+    }
+
+    /// `C` check character of the provided chars
+    pub fn c_check(chars: &[char]) -> char {
+        check_character(chars, 10)
+    }
+
+    /// `K` check character of the provided chars followed by their `C` check character
+    pub fn k_check(chars: &[char]) -> char {
+        check_character(chars, 9)
+    }
+}
+
+/// Sum of the values of the characters modulo 11, weighted from 1 to `max_weight` from the
+/// right, `-` being worth 10
+fn check_character(chars: &[char], max_weight: usize) -> char {
+    let sum = chars
+        .iter()
+        .filter_map(|&c| if c == '-' { Some(10) } else { c.to_digit(10) })
+        .rev()
+        .enumerate()
+        .map(|(i, value)| (i % max_weight + 1) * value as usize)
+        .sum::<usize>();
+    match sum % 11 {
+        10 => '-',
+        check => char::from(b'0' + check as u8),
+    }
+}
+
+impl DynamicBarcodeEncoding for Code11 {
+    fn encode(chars: &[char]) -> Result<BitVec, EncodingError> {
+        Ok(Code11::elements(chars, Code11::WIDTHS)?.modules())
     }
 }
@@ -0,0 +1,224 @@
+use bit_vec::BitVec;
+
+use super::{bound_rows, expand_widths, CODE_128_WIDTHS};
+use crate::{EncodingError, Symbol};
+
+/// Code 16K format, 2 to 16 rows of 5 Code 128 symbol characters between row indicators
+pub struct Code16K;
+
+/// Bar and space widths of the start and stop patterns
+const START_STOP: [[u8; 4]; 8] = [
+    [3, 2, 1, 1],
+    [2, 2, 2, 1],
+    [2, 1, 2, 2],
+    [1, 4, 1, 1],
+    [1, 1, 3, 2],
+    [1, 2, 3, 1],
+    [1, 1, 1, 4],
+    [3, 1, 1, 2],
+];
+
+/// Stop pattern of each row, the start pattern being the row number modulo 8
+const STOP_PATTERNS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 4, 5, 6, 7, 0, 1, 2, 3];
+
+/// Widths of symbol character 106, past the Code 128 ones
+const WIDTHS_106: [u8; 6] = [2, 1, 1, 1, 3, 3];
+
+const SHIFT: u8 = 98;
+const CODE_C: u8 = 99;
+const CODE_B: u8 = 100;
+const CODE_A: u8 = 101;
+const PAD: u8 = 103;
+
+/// Starting modes, the shifted modes encoding one or two characters in code set B before
+/// code set C
+const MODE_C_SHIFT_B: u8 = 5;
+const MODE_C_DOUBLE_SHIFT_B: u8 = 6;
+
+const MAX_ROWS: usize = 16;
+const ROW_HEIGHT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Set {
+    A,
+    B,
+    C,
+}
+
+const SETS: [Set; 3] = [Set::A, Set::B, Set::C];
+
+/// Way of encoding a character from a code set
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Direct,
+    Shift,
+    Latch(Set),
+}
+
+impl Code16K {
+    /// Encode ASCII data as a [`Symbol::Stacked`] of rows separated by bars, picking the
+    /// starting mode and the code set changes giving the fewest symbol characters
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        let bytes = data
+            .iter()
+            .map(|&c| u8::try_from(c).ok().filter(u8::is_ascii))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(EncodingError::WrongChar)?;
+        if bytes.is_empty() {
+            return Err(EncodingError::WrongSize);
+        }
+
+        let (mode, mut values) = code_16k_values(&bytes);
+
+        // pads up to full rows, with room for the two check characters
+        let rows = (values.len() + 2).div_ceil(5).max(2);
+        if rows > MAX_ROWS {
+            return Err(EncodingError::TooLong);
+        }
+        values.resize(5 * rows - 2, PAD);
+        values[0] = 7 * (rows as u8 - 2) + mode;
+
+        let (first, second) = values
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(first, second), (i, &v)| {
+                (first + (i + 2) * v as usize, second + (i + 1) * v as usize)
+            });
+        let first = first % 107;
+        let second = (second + first * (values.len() + 1)) % 107;
+        values.extend([first as u8, second as u8]);
+
+        let rows = values
+            .chunks(5)
+            .enumerate()
+            .map(|(row, characters)| {
+                let mut modules = Vec::with_capacity(70);
+                expand_widths(&mut modules, &START_STOP[row % 8], true);
+                modules.push(true);
+                for &value in characters {
+                    let widths = CODE_128_WIDTHS.get(value as usize).unwrap_or(&WIDTHS_106);
+                    expand_widths(&mut modules, widths, false);
+                }
+                expand_widths(&mut modules, &START_STOP[STOP_PATTERNS[row]], false);
+                BitVec::from_iter(modules)
+            })
+            .collect();
+        Ok(bound_rows(rows, ROW_HEIGHT))
+    }
+}
+
+/// Value of a character in a code set, digit pairs being taken in code set C
+fn value(bytes: &[u8], i: usize, set: Set) -> Option<u8> {
+    let byte = bytes[i];
+    match set {
+        Set::A if byte < 32 => Some(byte + 64),
+        Set::A if byte < 96 => Some(byte - 32),
+        Set::B if byte >= 32 => Some(byte - 32),
+        Set::C if byte.is_ascii_digit() => match bytes.get(i + 1) {
+            Some(next) if next.is_ascii_digit() => Some((byte - b'0') * 10 + next - b'0'),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Starting mode and symbol character values, the first one left for the mode character
+fn code_16k_values(bytes: &[u8]) -> (u8, Vec<u8>) {
+    let len = bytes.len();
+    let step = |set: Set| if set == Set::C { 2 } else { 1 };
+
+    // characters needed from `i` on in each code set, and how to encode the character at `i`
+    let mut costs = vec![[usize::MAX; 3]; len + 1];
+    costs[len] = [0; 3];
+    let mut steps = vec![[Step::Direct; 3]; len];
+    for i in (0..len).rev() {
+        let direct = |costs: &[[usize; 3]], set: Set| {
+            value(bytes, i, set)
+                .is_some()
+                .then(|| 1 + costs[i + step(set)][set as usize])
+                .filter(|&cost| cost < usize::MAX)
+        };
+        for set in SETS {
+            let mut best = direct(&costs, set).map(|cost| (cost, Step::Direct));
+            let mut consider = |cost: Option<usize>, step: Step| {
+                if let Some(cost) = cost {
+                    if best.is_none_or(|(best, _)| cost < best) {
+                        best = Some((cost, step));
+                    }
+                }
+            };
+            if set != Set::C {
+                let other = if set == Set::A { Set::B } else { Set::A };
+                let shifted = value(bytes, i, other).map(|_| 2 + costs[i + 1][set as usize]);
+                consider(shifted, Step::Shift);
+            }
+            for next in [Set::C, Set::B, Set::A] {
+                if next != set {
+                    consider(direct(&costs, next).map(|cost| cost + 1), Step::Latch(next));
+                }
+            }
+            if let Some((cost, step)) = best {
+                costs[i][set as usize] = cost;
+                steps[i][set as usize] = step;
+            }
+        }
+    }
+
+    // the shifted modes start with one or two characters of code set B then use code set C
+    let in_b = |n: usize| n < len && bytes[..=n].iter().all(|&b| b >= 32);
+    let starts = [
+        (Set::C as u8, Set::C, 0, costs[0][Set::C as usize]),
+        (Set::B as u8, Set::B, 0, costs[0][Set::B as usize]),
+        (Set::A as u8, Set::A, 0, costs[0][Set::A as usize]),
+        (
+            MODE_C_SHIFT_B,
+            Set::C,
+            1,
+            if in_b(0) {
+                1 + costs[1][Set::C as usize]
+            } else {
+                usize::MAX
+            },
+        ),
+        (
+            MODE_C_DOUBLE_SHIFT_B,
+            Set::C,
+            2,
+            if in_b(1) {
+                2 + costs[2][Set::C as usize]
+            } else {
+                usize::MAX
+            },
+        ),
+    ];
+    let (mode, mut set, shifted, _) = starts
+        .into_iter()
+        .min_by_key(|&(.., cost)| cost)
+        .unwrap_or(starts[0]);
+
+    let mut values = vec![0];
+    values.extend(bytes[..shifted].iter().map(|&b| b - 32));
+    let mut i = shifted;
+    while i < len {
+        match steps[i][set as usize] {
+            Step::Direct => {}
+            Step::Shift => {
+                let other = if set == Set::A { Set::B } else { Set::A };
+                values.extend([SHIFT, value(bytes, i, other).unwrap_or(0)]);
+                i += 1;
+                continue;
+            }
+            Step::Latch(next) => {
+                values.push(match next {
+                    Set::A => CODE_A,
+                    Set::B => CODE_B,
+                    Set::C => CODE_C,
+                });
+                set = next;
+            }
+        }
+        values.push(value(bytes, i, set).unwrap_or(0));
+        i += step(set);
+    }
+    (mode, values)
+}
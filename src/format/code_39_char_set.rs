@@ -0,0 +1,51 @@
+use bit_vec::BitVec;
+
+use crate::EncodingError;
+
+/// Code 39 character set, the start and stop character `*` following it
+pub(crate) const CODE_39_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+/// Wide elements of each character of [`CODE_39_CHARS`] and of `*`, alternately bars and
+/// spaces, the first element in bit 8
+const CODE_39_WIDE: [u16; 44] = [
+    0x034, 0x121, 0x061, 0x160, 0x031, 0x130, 0x070, 0x025, 0x124, 0x064, 0x109, 0x049, 0x148,
+    0x019, 0x118, 0x058, 0x00D, 0x10C, 0x04C, 0x01C, 0x103, 0x043, 0x142, 0x013, 0x112, 0x052,
+    0x007, 0x106, 0x046, 0x016, 0x181, 0x0C1, 0x1C0, 0x091, 0x190, 0x0D0, 0x085, 0x184, 0x0C4,
+    0x0A8, 0x0A2, 0x08A, 0x02A, 0x094,
+];
+
+const CODE_39_START_STOP: usize = 43;
+
+/// Width of the Code 39 wide elements in narrow ones, within the 2.2 to 3.0 of ISO/IEC 16388
+/// for any X-dimension
+pub(crate) const CODE_39_RATIO: usize = 3;
+
+/// Modules of the Code 39 characters between the start and stop characters, wide elements
+/// being [`CODE_39_RATIO`] modules wide and characters separated by a narrow space
+pub(crate) fn code_39_modules(data: &[char]) -> Result<BitVec, EncodingError> {
+    let indices = data
+        .iter()
+        .map(|&c| CODE_39_CHARS.find(c))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(EncodingError::WrongChar)?;
+
+    let mut modules = BitVec::new();
+    let characters = [CODE_39_START_STOP]
+        .into_iter()
+        .chain(indices)
+        .chain([CODE_39_START_STOP]);
+    for (i, index) in characters.enumerate() {
+        if i > 0 {
+            modules.push(false);
+        }
+        for element in 0..9 {
+            let width = if CODE_39_WIDE[index] >> (8 - element) & 1 == 1 {
+                CODE_39_RATIO
+            } else {
+                1
+            };
+            modules.grow(width, element % 2 == 0);
+        }
+    }
+    Ok(modules)
+}
@@ -0,0 +1,205 @@
+use bit_vec::BitVec;
+
+use super::{bound_rows, push_bits, CODE_49_ASCII, CODE_49_EVEN, CODE_49_ODD};
+use crate::{EncodingError, Symbol};
+
+/// Code 49 format, 2 to 8 rows of 4 symbol characters, each row ending with a row check
+/// character and the last row holding the symbol check characters
+pub struct Code49;
+
+/// Code 49 character set, followed by Shift 1, Shift 2, FNC1, FNC2, FNC3 and Numeric Shift
+const CODE_49_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%!&";
+
+const SHIFT_1: u8 = 43;
+const SHIFT_2: u8 = 44;
+const NUMERIC_SHIFT: u8 = 48;
+
+/// Starting modes, beside the alphanumeric mode 0, entering numeric mode or shifting the
+/// first character
+const MODE_NUMERIC: u8 = 2;
+const MODE_SHIFT_1: u8 = 4;
+const MODE_SHIFT_2: u8 = 5;
+
+/// Weights of the X, Y and Z symbol check characters, by position of the pair of code
+/// characters
+const X_WEIGHTS: [usize; 32] = [
+    1, 9, 31, 26, 2, 12, 17, 23, 37, 18, 22, 6, 27, 44, 15, 43, 39, 11, 13, 5, 41, 33, 36, 8, 4,
+    32, 3, 19, 40, 25, 29, 10,
+];
+const Y_WEIGHTS: [usize; 32] = [
+    9, 31, 26, 2, 12, 17, 23, 37, 18, 22, 6, 27, 44, 15, 43, 39, 11, 13, 5, 41, 33, 36, 8, 4, 32,
+    3, 19, 40, 25, 29, 10, 24,
+];
+const Z_WEIGHTS: [usize; 32] = [
+    31, 26, 2, 12, 17, 23, 37, 18, 22, 6, 27, 44, 15, 43, 39, 11, 13, 5, 41, 33, 36, 8, 4, 32, 3,
+    19, 40, 25, 29, 10, 24, 30,
+];
+
+/// Odd parity of the symbol characters of each row but the last one, whose characters all
+/// have even parity
+const ODD_PARITY: [[bool; 4]; 7] = [
+    [true, false, false, true],
+    [false, true, false, true],
+    [true, true, false, false],
+    [false, false, true, true],
+    [true, false, true, false],
+    [false, true, true, false],
+    [true, true, true, true],
+];
+
+const MAX_CODEWORDS: usize = 49;
+const ROW_HEIGHT: usize = 10;
+
+impl Code49 {
+    /// Encode ASCII data as a [`Symbol::Stacked`] of rows separated by bars, runs of at least
+    /// 5 digits being encoded in numeric mode
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        if data.is_empty() {
+            return Err(EncodingError::WrongSize);
+        }
+        let characters = data
+            .iter()
+            .map(|&c| CODE_49_ASCII.get(c as usize).copied())
+            .collect::<Option<String>>()
+            .ok_or(EncodingError::WrongChar)?
+            .chars()
+            .collect::<Vec<_>>();
+
+        let mut codewords = code_49_codewords(&characters);
+        let mode = match codewords[0] {
+            NUMERIC_SHIFT => MODE_NUMERIC,
+            SHIFT_1 => MODE_SHIFT_1,
+            SHIFT_2 => MODE_SHIFT_2,
+            _ => 0,
+        };
+        if mode != 0 {
+            codewords.remove(0);
+        }
+        if codewords.len() > MAX_CODEWORDS {
+            return Err(EncodingError::TooLong);
+        }
+
+        // rows of 7 code characters, the last row taking at most 2 data characters before the
+        // symbol check characters
+        let mut rows = codewords.len().div_ceil(7).max(1);
+        let last_data = codewords.len() - 7 * (rows - 1);
+        if rows == 1 || rows > 6 || last_data > 2 {
+            rows += 1;
+        }
+        let mut grid = vec![[NUMERIC_SHIFT as usize; 8]; rows];
+        for (i, &codeword) in codewords.iter().enumerate() {
+            grid[i / 7][i % 7] = codeword as usize;
+        }
+        let last = rows - 1;
+        grid[last][6] = 7 * (rows - 2) + mode as usize;
+        for row in &mut grid[..last] {
+            row[7] = row[..7].iter().sum::<usize>() % 49;
+        }
+
+        let pair = |row: &[usize; 8], i: usize| row[2 * i] * 49 + row[2 * i + 1];
+        let (mut x, mut y, mut z) = (grid[last][6] * 20, grid[last][6] * 16, grid[last][6] * 38);
+        for (position, value) in grid[..last]
+            .iter()
+            .flat_map(|row| (0..4).map(move |i| pair(row, i)))
+            .enumerate()
+        {
+            x += X_WEIGHTS[position] * value;
+            y += Y_WEIGHTS[position] * value;
+            z += Z_WEIGHTS[position] * value;
+        }
+        let mut position = 4 * last;
+        if rows > 6 {
+            z %= 2401;
+            grid[last][..2].copy_from_slice(&[z / 49, z % 49]);
+        }
+        let value = pair(&grid[last], 0);
+        x += X_WEIGHTS[position] * value;
+        y += Y_WEIGHTS[position] * value;
+        position += 1;
+        y %= 2401;
+        grid[last][2..4].copy_from_slice(&[y / 49, y % 49]);
+        x += X_WEIGHTS[position] * pair(&grid[last], 1);
+        x %= 2401;
+        grid[last][4..6].copy_from_slice(&[x / 49, x % 49]);
+        grid[last][7] = grid[last][..7].iter().sum::<usize>() % 49;
+
+        let rows = grid
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                let mut modules = vec![true, false];
+                for i in 0..4 {
+                    let odd = r < last && ODD_PARITY[r][i];
+                    let table = if odd { &CODE_49_ODD } else { &CODE_49_EVEN };
+                    push_bits(&mut modules, table[pair(row, i)] as u32, 16);
+                }
+                modules.extend([true; 4]);
+                BitVec::from_iter(modules)
+            })
+            .collect();
+        Ok(bound_rows(rows, ROW_HEIGHT))
+    }
+}
+
+/// Code characters of the Code 49 characters, switching to numeric mode for runs of at least 5
+/// digits
+fn code_49_codewords(characters: &[char]) -> Vec<u8> {
+    let value = |c: char| CODE_49_CHARS.find(c).unwrap_or(0) as u8;
+    let mut codewords = Vec::new();
+    let mut i = 0;
+    while i < characters.len() {
+        let digits = characters[i..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        // digits following a shift belong to it
+        let shifted = i > 0 && matches!(characters[i - 1], '!' | '&');
+        if digits < 5 || shifted {
+            codewords.push(value(characters[i]));
+            i += 1;
+            continue;
+        }
+
+        codewords.push(NUMERIC_SHIFT);
+        let number = |from: usize, len: usize| {
+            characters[from..from + len]
+                .iter()
+                .fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap_or(0) as usize)
+        };
+        let mut push_base_48 = |value: usize, len: u32| {
+            codewords.extend((0..len).rev().map(|i| (value / 48usize.pow(i) % 48) as u8));
+        };
+        // blocks of 5 digits in 3 code characters, 4 digits taking 3 code characters and
+        // 3 digits 2 of them
+        let (blocks, remainder) = (digits / 5, digits % 5);
+        for block in 0..blocks {
+            if block == blocks - 1 && remainder == 2 {
+                push_base_48(100000 + number(i, 4), 3);
+                push_base_48(number(i + 4, 3), 2);
+                i += 7;
+            } else {
+                push_base_48(number(i, 5), 3);
+                i += 5;
+            }
+        }
+        match remainder {
+            1 => {
+                codewords.push(value(characters[i]));
+                i += 1;
+            }
+            3 => {
+                push_base_48(number(i, 3), 2);
+                i += 3;
+            }
+            4 => {
+                push_base_48(100000 + number(i, 4), 3);
+                i += 4;
+            }
+            _ => {}
+        }
+        if i < characters.len() {
+            codewords.push(NUMERIC_SHIFT);
+        }
+    }
+    codewords
+}
@@ -0,0 +1,711 @@
+use bit_vec::BitVec;
+
+use super::{
+    code_128_modules, date_value, general_field_encode, gs1_128_values, gs1_check_digit,
+    gs1_reduce, push_bits, Code128Set, GeneralFieldMode, Parity, StaticBarcodeEncoding, CC_A_SIZES,
+    CC_A_START_RAPS, EAN13, EAN8, GS1_FNC1, MICRO_PDF417_RAP_CENTRE, MICRO_PDF417_RAP_SIDE,
+    MICRO_PDF417_SIZES, MICRO_PDF417_START_RAPS, PDF417_CODEWORDS,
+};
+use crate::{EncodingError, StackedRow, Symbol};
+
+/// GS1 Composite format, a MicroPDF417 or PDF417 based 2D component stacked on top of a linear
+/// component, ISO/IEC 24723
+///
+/// The 2D component carries a GS1 element string with its AIs in brackets, as
+/// `(10)ABC123(17)251231`, the separator pattern and linkage flag tying it to the linear
+/// component.
+pub struct Composite;
+
+/// Linear component of a [`Composite`] symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeLinear {
+    /// EAN-13 of 12 digits, or 13 with the check digit
+    EAN13,
+    /// EAN-8 of 7 digits, or 8 with the check digit
+    EAN8,
+    /// UPC-A of 11 digits, or 12 with the check digit
+    UPCA,
+    /// UPC-E of 6 digits, 7 with a leading number system of 0 or 1, or 8 with the check digit
+    UPCE,
+    /// GS1-128 of a GS1 element string with its AIs in brackets
+    GS1128,
+}
+
+/// 2D component of a [`Composite`] symbol, from the smallest to the largest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompositeComponent {
+    /// CC-A, a MicroPDF417 variant of up to 56 digits
+    CcA,
+    /// CC-B, MicroPDF417 of up to 338 digits
+    CcB,
+    /// CC-C, PDF417 of up to 2361 digits, only above GS1-128
+    CcC,
+}
+
+/// Height of a composite symbol, the linear component taking what the 2D component leaves
+const HEIGHT: usize = 50;
+
+/// Parity of the UPC-E digits for number system 0 by check digit, a set bit for even parity and
+/// the first digit in the highest bit, number system 1 using the opposite parities
+const UPC_E_PARITY: [u8; 10] = [
+    0b111000, 0b110100, 0b110010, 0b110001, 0b101100, 0b100110, 0b100011, 0b101010, 0b101001,
+    0b100101,
+];
+
+impl Composite {
+    /// Encode the primary message in the linear component and the `composite` element string
+    /// in the smallest 2D component holding it, as [`Symbol::Stacked`] rows
+    pub fn encode(
+        linear: CompositeLinear,
+        primary: &[char],
+        composite: &[char],
+    ) -> Result<Symbol, EncodingError> {
+        Self::encode_component(linear, CompositeComponent::CcA, primary, composite)
+    }
+
+    /// Encode as [`Composite::encode`], the 2D component being at least `component`.
+    ///
+    /// [`CompositeComponent::CcC`] only exists above GS1-128, any other linear component giving
+    /// [`EncodingError::TooLong`].
+    pub fn encode_component(
+        linear: CompositeLinear,
+        component: CompositeComponent,
+        primary: &[char],
+        composite: &[char],
+    ) -> Result<Symbol, EncodingError> {
+        let reduced = gs1_reduce(composite)?;
+
+        let (linear_modules, gs1_128) = if linear == CompositeLinear::GS1128 {
+            let (values, last_set) = gs1_128_values(&gs1_reduce(primary)?);
+            // with the linkage flag, at most 102 characters before the check character
+            if values.len() + 1 > 102 {
+                return Err(EncodingError::TooLong);
+            }
+            (BitVec::new(), Some((values, last_set)))
+        } else {
+            (ean_upc_modules(linear, primary)?, None)
+        };
+        let linear_width = match &gs1_128 {
+            Some((values, _)) => 11 * (values.len() + 2) + 13,
+            None => linear_modules.len(),
+        };
+        let columns = match linear {
+            CompositeLinear::EAN13 | CompositeLinear::UPCA | CompositeLinear::GS1128 => 4,
+            CompositeLinear::EAN8 => 3,
+            CompositeLinear::UPCE => 2,
+        };
+
+        let largest = match linear {
+            CompositeLinear::GS1128 => CompositeComponent::CcC,
+            _ => CompositeComponent::CcB,
+        };
+        if component > largest {
+            return Err(EncodingError::TooLong);
+        }
+        let mut component = component;
+        let (bits, columns, ecc_level) = loop {
+            match component_bits(&reduced, component, columns, linear_width) {
+                Err(EncodingError::TooLong) if component < largest => {
+                    component = match component {
+                        CompositeComponent::CcA => CompositeComponent::CcB,
+                        _ => CompositeComponent::CcC,
+                    }
+                }
+                result => break result?,
+            }
+        };
+
+        let component_rows = match component {
+            CompositeComponent::CcA => cc_a_rows(&bits, columns),
+            CompositeComponent::CcB => cc_b_rows(&bits, columns),
+            CompositeComponent::CcC => cc_c_rows(&bits, columns, ecc_level),
+        };
+        let component_width = component_rows[0].modules.len();
+        let component_height = component_rows.iter().map(|r| r.height).sum::<usize>();
+
+        // the 2D component is aligned with the linear one, ISO/IEC 24723 12.3
+        let (linear_rows, top_shift, bottom_shift) = match gs1_128 {
+            Some((mut values, last_set)) => {
+                values.push(match (component, last_set) {
+                    (CompositeComponent::CcC, Code128Set::B) => 101,
+                    (CompositeComponent::CcC, Code128Set::C) => 100,
+                    (_, Code128Set::B) => 99,
+                    (_, Code128Set::C) => 101,
+                });
+                let modules = code_128_modules(&values);
+                let separator = modules.iter().map(|&m| !m).collect();
+                let height = HEIGHT.saturating_sub(component_height + 1).max(1);
+                let rows = vec![row(separator, 1), row(modules, height)];
+
+                let shift = if component == CompositeComponent::CcC {
+                    -7
+                } else {
+                    // aligned with the last space of the character at position
+                    // (characters - 9) / 2, counting from the stop character on the right
+                    let characters = (linear_width as isize - 2) / 11;
+                    let position = (characters - 9) / 2;
+                    let shift =
+                        linear_width as isize - position * 11 - 1 - component_width as isize;
+                    if position != 0 {
+                        shift - 2
+                    } else {
+                        shift
+                    }
+                };
+                (rows, shift.max(0) as usize, (-shift).max(0) as usize)
+            }
+            None => {
+                let width = linear_modules.len();
+                let separator = |outer: bool| {
+                    let (left, right) = if outer { (1, width) } else { (0, width + 1) };
+                    (0..width + 2).map(|i| i == left || i == right).collect()
+                };
+                let mut modules = vec![false];
+                modules.extend(linear_modules);
+                modules.push(false);
+                let height = HEIGHT.saturating_sub(component_height + 6).max(1);
+                let rows = vec![
+                    row(separator(true), 2),
+                    row(separator(false), 2),
+                    row(separator(true), 2),
+                    row(modules, height),
+                ];
+                let shift = match (linear, component) {
+                    (CompositeLinear::EAN8, CompositeComponent::CcA) => 3,
+                    (CompositeLinear::EAN8, _) => 13,
+                    _ => 2,
+                };
+                (rows, 0, shift)
+            }
+        };
+
+        let width = (component_width + top_shift).max(linear_rows[0].modules.len() + bottom_shift);
+        let shifted = |row: StackedRow, shift: usize| StackedRow {
+            modules: (0..width)
+                .map(|i| i >= shift && row.modules.get(i - shift).unwrap_or(false))
+                .collect(),
+            height: row.height,
+        };
+        let rows = component_rows
+            .into_iter()
+            .map(|r| shifted(r, top_shift))
+            .chain(linear_rows.into_iter().map(|r| shifted(r, bottom_shift)))
+            .collect();
+        Ok(Symbol::Stacked(rows))
+    }
+}
+
+fn row(modules: Vec<bool>, height: usize) -> StackedRow {
+    StackedRow {
+        modules: BitVec::from_iter(modules),
+        height,
+    }
+}
+
+/// Modules of an EAN or UPC linear component
+fn ean_upc_modules(linear: CompositeLinear, primary: &[char]) -> Result<BitVec, EncodingError> {
+    if !primary.iter().all(char::is_ascii_digit) {
+        return Err(EncodingError::WrongChar);
+    }
+    let upc_a = || ['0'].iter().chain(primary).copied().collect::<Vec<_>>();
+    Ok(match (linear, primary.len()) {
+        (CompositeLinear::EAN13, 12) => EAN13::encode(TryInto::<&[_; 12]>::try_into(primary)?)?,
+        (CompositeLinear::EAN13, 13) => EAN13::encode(TryInto::<&[_; 13]>::try_into(primary)?)?,
+        (CompositeLinear::UPCA, 11) => EAN13::encode(TryInto::<&[_; 12]>::try_into(&upc_a()[..])?)?,
+        (CompositeLinear::UPCA, 12) => EAN13::encode(TryInto::<&[_; 13]>::try_into(&upc_a()[..])?)?,
+        (CompositeLinear::EAN8, 7) => EAN8::encode(TryInto::<&[_; 7]>::try_into(primary)?)?,
+        (CompositeLinear::EAN8, 8) => EAN8::encode(TryInto::<&[_; 8]>::try_into(primary)?)?,
+        (CompositeLinear::UPCE, 6..=8) => upc_e(primary)?,
+        _ => return Err(EncodingError::WrongSize),
+    })
+}
+
+/// Modules of a UPC-E, ISO/IEC 15420 5.2.2.4
+fn upc_e(primary: &[char]) -> Result<BitVec, EncodingError> {
+    let mut digits = primary.iter().map(|&c| c as u8).collect::<Vec<_>>();
+    let check_digit = if digits.len() == 8 {
+        digits.pop()
+    } else {
+        None
+    };
+    if digits.len() == 6 {
+        digits.insert(0, b'0');
+    }
+    let number_system = digits[0];
+    if number_system > b'1' {
+        return Err(EncodingError::WrongChar);
+    }
+    let digits = &digits[1..];
+
+    // zero-suppressed digits of the UPC-A equivalent, ISO/IEC 15420 Table 5
+    let mut equivalent = [b'0'; 11];
+    equivalent[0] = number_system;
+    equivalent[1..3].copy_from_slice(&digits[..2]);
+    match digits[5] {
+        b'0'..=b'2' => {
+            equivalent[3] = digits[5];
+            equivalent[8..].copy_from_slice(&digits[2..5]);
+        }
+        b'3' if digits[2] > b'2' => {
+            equivalent[3] = digits[2];
+            equivalent[9..].copy_from_slice(&digits[3..5]);
+        }
+        b'4' if digits[3] != b'0' => {
+            equivalent[3..5].copy_from_slice(&digits[2..4]);
+            equivalent[10] = digits[4];
+        }
+        b'5'..=b'9' if digits[4] != b'0' => {
+            equivalent[3..6].copy_from_slice(&digits[2..5]);
+            equivalent[10] = digits[5];
+        }
+        _ => return Err(EncodingError::WrongChar),
+    }
+    let check = gs1_check_digit(&equivalent);
+    if check_digit.is_some_and(|c| c != check) {
+        return Err(EncodingError::WrongCheckusm);
+    }
+
+    let mut even = UPC_E_PARITY[(check - b'0') as usize];
+    if number_system == b'1' {
+        even ^= 0b111111;
+    }
+    let mut modules = BitVec::from_iter([true, false, true]);
+    for (i, &digit) in digits.iter().enumerate() {
+        let parity = if even >> (5 - i) & 1 == 1 {
+            Parity::Even
+        } else {
+            Parity::Odd
+        };
+        modules.extend(parity.encode(digit as char));
+    }
+    modules.extend([false, true, false, true, false, true]);
+    Ok(modules)
+}
+
+/// Bits of the reduced element string padded to the size of the `component`, ISO/IEC 24723 5,
+/// with the number of columns and the error correction level of a CC-C
+fn component_bits(
+    reduced: &[u8],
+    component: CompositeComponent,
+    columns: usize,
+    linear_width: usize,
+) -> Result<(Vec<bool>, usize, u32), EncodingError> {
+    let mut bits = Vec::new();
+    let mut mode = GeneralFieldMode::Numeric;
+    let mut read_position = 0;
+    let mut fnc1 = false;
+    let mut alpha_padding = false;
+    let mut cropped = None;
+
+    let date = reduced.len() > 1
+        && reduced[0] == b'1'
+        && (reduced[1] == b'0'
+            || (matches!(reduced[1], b'1' | b'7') && date_value(reduced, 2).is_some()));
+    if date {
+        // lot number, production or expiration date, 5.3.1
+        push_bits(&mut bits, 2, 2);
+        if reduced[1] == b'0' {
+            push_bits(&mut bits, 3, 2);
+            read_position = 2;
+        } else {
+            push_bits(&mut bits, date_value(reduced, 2).unwrap(), 16);
+            bits.push(reduced[1] == b'7');
+            read_position = 8;
+            if reduced[8..].starts_with(b"10") {
+                read_position = 10;
+            } else if reduced.len() > 8 {
+                fnc1 = true;
+            } else {
+                // an FNC1 still ends the date, through an alphanumeric latch
+                push_bits(&mut bits, 15, 9);
+            }
+        }
+    } else if let Some(ai_90) = ai_90_compaction(reduced, &mut bits, &mut mode) {
+        (read_position, cropped, alpha_padding) = ai_90;
+    } else {
+        bits.push(false);
+    }
+
+    let mut field = Vec::with_capacity(reduced.len() + 1);
+    if fnc1 {
+        field.push(GS1_FNC1);
+    }
+    field.extend(
+        reduced
+            .iter()
+            .enumerate()
+            .skip(read_position)
+            .filter(|&(i, _)| cropped.is_none_or(|(start, len)| i < start || i >= start + len))
+            .map(|(_, &b)| b),
+    );
+    let mut last_digit = None;
+    if !field.is_empty() {
+        alpha_padding = false;
+        last_digit = general_field_encode(&field, &mut mode, &mut bits)?;
+    }
+
+    let size = |len| padded_size(component, len, columns, linear_width);
+    if let Some(digit) = last_digit {
+        let (target, _, _) = size(bits.len()).ok_or(EncodingError::TooLong)?;
+        let digit = (digit - b'0') as u32;
+        let remainder = target - bits.len();
+        if (4..=6).contains(&remainder) {
+            push_bits(&mut bits, digit + 1, 4);
+            push_bits(&mut bits, 0, remainder - 4);
+        } else {
+            push_bits(&mut bits, 11 * digit + 18, 7);
+        }
+    }
+    let (target, columns, ecc_level) = size(bits.len()).ok_or(EncodingError::TooLong)?;
+    if bits.len() < target {
+        if alpha_padding {
+            push_bits(&mut bits, 31, 5);
+        }
+        if mode == GeneralFieldMode::Numeric {
+            push_bits(&mut bits, 0, 4);
+        }
+        while bits.len() < target {
+            push_bits(&mut bits, 4, 5);
+        }
+        bits.truncate(target);
+    }
+    Ok((bits, columns, ecc_level))
+}
+
+/// Compact a leading AI 90 starting with up to 3 digits and an uppercase letter, 5.3.2.
+///
+/// Returns where the general-purpose field starts, the AI 21 or 8004 left out of it and
+/// whether an FNC1 pads the alpha encodation.
+#[allow(clippy::type_complexity)]
+fn ai_90_compaction(
+    reduced: &[u8],
+    bits: &mut Vec<bool>,
+    mode: &mut GeneralFieldMode,
+) -> Option<(usize, Option<(usize, usize)>, bool)> {
+    if !reduced.starts_with(b"90") || reduced.len() < 3 {
+        return None;
+    }
+    let end = reduced[3..]
+        .iter()
+        .position(|&b| b == GS1_FNC1)
+        .map_or(reduced.len(), |p| p + 3);
+    let data = &reduced[2..end];
+    if data[0] == b'0' {
+        return None;
+    }
+    let letter = data
+        .iter()
+        .take(4)
+        .position(|b| !b.is_ascii_digit())
+        .filter(|&i| data[i].is_ascii_uppercase())?;
+
+    let upper = data.iter().filter(|b| b.is_ascii_uppercase()).count() - 1;
+    let digits = data.iter().filter(|b| b.is_ascii_digit()).count() - letter;
+    let other = data.len() - upper - 1 - digits - letter;
+    push_bits(bits, 3, 2);
+    let alpha = other == 0 && upper > digits;
+    if alpha {
+        push_bits(bits, 3, 2);
+    } else if other == 0 && upper == 0 {
+        push_bits(bits, 2, 2);
+    } else {
+        bits.push(false);
+        *mode = GeneralFieldMode::Alphanumeric;
+    }
+
+    let cropped = if reduced.get(end) != Some(&GS1_FNC1) {
+        None
+    } else if reduced[end + 1..].starts_with(b"21") {
+        Some((end + 1, 2))
+    } else if reduced[end + 1..].starts_with(b"8004") {
+        Some((end + 1, 4))
+    } else {
+        None
+    };
+    match cropped {
+        None => bits.push(false),
+        Some((_, 2)) => push_bits(bits, 2, 2),
+        Some(_) => push_bits(bits, 3, 2),
+    }
+
+    let value = data[..letter]
+        .iter()
+        .fold(0, |v, &d| v * 10 + (d - b'0') as u32);
+    match b"BDHIJKLNPQRSTVWZ"
+        .iter()
+        .position(|&l| l == data[letter])
+        .filter(|_| value < 31)
+    {
+        Some(index) => {
+            push_bits(bits, value, 5);
+            push_bits(bits, index as u32, 4);
+        }
+        None => {
+            push_bits(bits, 31, 5);
+            push_bits(bits, value, 10);
+            push_bits(bits, (data[letter] - b'A') as u32, 5);
+        }
+    }
+
+    let mut read_position = letter + 3;
+    if alpha {
+        // alpha encodation of the rest of the AI 90 data and its FNC1, 5.3.3
+        loop {
+            let byte = reduced.get(read_position).copied();
+            match byte {
+                Some(b @ b'A'..=b'Z') => push_bits(bits, (b - b'A') as u32, 5),
+                Some(b @ b'0'..=b'9') => push_bits(bits, b as u32 + 4, 6),
+                Some(GS1_FNC1) => push_bits(bits, 31, 5),
+                _ => {}
+            }
+            read_position += 1;
+            if matches!(byte, None | Some(GS1_FNC1)) {
+                break;
+            }
+        }
+    }
+    Some((read_position, cropped, alpha))
+}
+
+/// Bits, columns and error correction level of the smallest size of the `component` holding
+/// `len` bits
+fn padded_size(
+    component: CompositeComponent,
+    len: usize,
+    columns: usize,
+    linear_width: usize,
+) -> Option<(usize, usize, u32)> {
+    // bits held by byte compaction codewords, 5 for each 6 bytes
+    let byte_bits = |codewords: usize| 8 * (6 * (codewords / 5) + codewords % 5);
+    match component {
+        CompositeComponent::CcA => CC_A_SIZES
+            .iter()
+            .filter(|s| s.0 as usize == columns)
+            .map(|s| {
+                // 7 codewords for each 69 bits
+                let data = s.1 as usize;
+                69 * (data / 7) + (10 * (data % 7)).saturating_sub(1)
+            })
+            .find(|&bits| len <= bits)
+            .map(|bits| (bits, columns, 0)),
+        CompositeComponent::CcB => MICRO_PDF417_SIZES
+            .iter()
+            .filter(|s| s.0 as usize == columns)
+            // less the CC-B identifier and the byte compaction latch
+            .map(|&(c, r, e)| byte_bits(c as usize * r as usize - e as usize - 2))
+            .find(|&bits| len <= bits)
+            .map(|bits| (bits, columns, 0)),
+        CompositeComponent::CcC => {
+            let bytes = len.div_ceil(8);
+            let mut codewords = 5 * (bytes / 6) + bytes % 6;
+            // recommended error correction levels, ISO/IEC 15438 Annex E, within 30 by 30
+            // codewords
+            let ecc_level = match codewords {
+                0..=40 => 2,
+                41..=160 => 3,
+                161..=320 => 4,
+                321..=833 => 5,
+                834..=865 => 4,
+                _ => return None,
+            };
+            let ecc_codewords = 1 << (ecc_level + 1);
+            codewords += ecc_codewords + 3;
+
+            // as wide as the linear component allows, 12.3 f), unless too high
+            let mut columns = if linear_width == 68 {
+                1
+            } else {
+                ((linear_width - 52) / 17).min(30)
+            };
+            while codewords.div_ceil(columns) > 30 && columns < 30 {
+                columns += 1;
+            }
+            let rows = codewords.div_ceil(columns);
+            if rows > 30 {
+                return None;
+            }
+            let data = columns * rows.max(3) - ecc_codewords - 3;
+            Some((byte_bits(data), columns, ecc_level))
+        }
+    }
+}
+
+/// Bytes of the bits, most significant bit first
+fn to_bytes(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|byte| byte.iter().fold(0, |b, &bit| b << 1 | bit as u8))
+        .collect()
+}
+
+/// Append the byte compaction of the bytes, ISO/IEC 15438 5.4.3
+fn byte_compaction(codewords: &mut Vec<u16>, bytes: &[u8]) {
+    codewords.push(if bytes.len().is_multiple_of(6) {
+        924
+    } else {
+        901
+    });
+    for chunk in bytes.chunks(6) {
+        if chunk.len() < 6 {
+            codewords.extend(chunk.iter().map(|&b| b as u16));
+            continue;
+        }
+        let mut value = chunk.iter().fold(0u64, |v, &b| v << 8 | b as u64);
+        let start = codewords.len();
+        codewords.resize(start + 5, 0);
+        for codeword in codewords[start..].iter_mut().rev() {
+            *codeword = (value % 900) as u16;
+            value /= 900;
+        }
+    }
+}
+
+/// Error correction codewords of the data, the generator polynomial having roots `3^1` to
+/// `3^count` modulo 929, ISO/IEC 15438 5.8
+fn pdf417_ecc(data: &[u16], count: usize) -> Vec<u16> {
+    // coefficients lowest degree first, without the leading one
+    let mut generator = vec![1u32];
+    let mut root = 1;
+    for _ in 0..count {
+        root = root * 3 % 929;
+        let mut next = vec![0; generator.len() + 1];
+        for (j, &coefficient) in generator.iter().enumerate() {
+            next[j + 1] = (next[j + 1] + coefficient) % 929;
+            next[j] = (next[j] + 929 - root * coefficient % 929) % 929;
+        }
+        generator = next;
+    }
+
+    let mut ecc = vec![0u32; count];
+    for &d in data {
+        let total = (d as u32 + ecc[count - 1]) % 929;
+        for j in (0..count).rev() {
+            let previous = if j == 0 { 0 } else { ecc[j - 1] };
+            ecc[j] = (previous + 929 - total * generator[j] % 929) % 929;
+        }
+    }
+    ecc.iter()
+        .rev()
+        .map(|&e| ((929 - e) % 929) as u16)
+        .collect()
+}
+
+/// Rows of MicroPDF417 codewords, each starting with the given row address patterns and
+/// cluster, the left pattern being left out of 3 column CC-A rows
+fn micro_pdf417_rows(
+    codewords: &[u16],
+    columns: usize,
+    start_raps: (u8, u8, u8, u8),
+    left_rap: bool,
+) -> Vec<StackedRow> {
+    let (mut left, mut centre, mut right) = (
+        start_raps.0 as usize,
+        start_raps.1 as usize,
+        start_raps.2 as usize,
+    );
+    let mut cluster = start_raps.3 as usize / 3;
+    codewords
+        .chunks(columns)
+        .map(|codewords| {
+            let mut modules = Vec::new();
+            if left_rap {
+                push_bits(&mut modules, MICRO_PDF417_RAP_SIDE[left - 1] as u32, 10);
+            }
+            for (i, &codeword) in codewords.iter().enumerate() {
+                if (columns == 3 && i == 1) || (columns == 4 && i == 2) {
+                    push_bits(&mut modules, MICRO_PDF417_RAP_CENTRE[centre - 1] as u32, 10);
+                }
+                push_bits(
+                    &mut modules,
+                    PDF417_CODEWORDS[cluster][codeword as usize] as u32,
+                    16,
+                );
+                modules.push(false);
+            }
+            push_bits(&mut modules, MICRO_PDF417_RAP_SIDE[right - 1] as u32, 10);
+            modules.push(true);
+
+            left = left % 52 + 1;
+            centre = centre % 52 + 1;
+            right = right % 52 + 1;
+            cluster = (cluster + 1) % 3;
+            row(modules, 2)
+        })
+        .collect()
+}
+
+/// Rows of a CC-A, ISO/IEC 24723 6
+fn cc_a_rows(bits: &[bool], columns: usize) -> Vec<StackedRow> {
+    // each 69 bits, or what is left, as base 928 codewords
+    let mut codewords = Vec::new();
+    for group in bits.chunks(69) {
+        let mut value = group.iter().fold(0u128, |v, &b| v << 1 | b as u128);
+        let start = codewords.len();
+        codewords.resize(start + group.len() / 10 + 1, 0);
+        for codeword in codewords[start..].iter_mut().rev() {
+            *codeword = (value % 928) as u16;
+            value /= 928;
+        }
+    }
+
+    let size = CC_A_SIZES
+        .iter()
+        .position(|s| s.0 as usize == columns && s.1 as usize == codewords.len())
+        .unwrap();
+    codewords.extend(pdf417_ecc(&codewords, CC_A_SIZES[size].3 as usize));
+    micro_pdf417_rows(&codewords, columns, CC_A_START_RAPS[size], columns != 3)
+}
+
+/// Rows of a CC-B, ISO/IEC 24723 7
+fn cc_b_rows(bits: &[bool], columns: usize) -> Vec<StackedRow> {
+    let mut codewords = vec![920];
+    byte_compaction(&mut codewords, &to_bytes(bits));
+
+    let size = MICRO_PDF417_SIZES
+        .iter()
+        .position(|&(c, r, e)| {
+            c as usize == columns && c as usize * r as usize - e as usize >= codewords.len()
+        })
+        .unwrap();
+    let (c, r, e) = MICRO_PDF417_SIZES[size];
+    codewords.resize(c as usize * r as usize - e as usize, 900);
+    codewords.extend(pdf417_ecc(&codewords, e as usize));
+    micro_pdf417_rows(&codewords, columns, MICRO_PDF417_START_RAPS[size], true)
+}
+
+/// Rows of a CC-C, ISO/IEC 24723 8
+fn cc_c_rows(bits: &[bool], columns: usize, ecc_level: u32) -> Vec<StackedRow> {
+    let mut codewords = vec![0, 920];
+    byte_compaction(&mut codewords, &to_bytes(bits));
+    codewords[0] = codewords.len() as u16;
+    codewords.extend(pdf417_ecc(&codewords, 1 << (ecc_level + 1)));
+
+    // row indicators, ISO/IEC 15438 5.7
+    let rows = codewords.len() / columns;
+    let indicators = [
+        (rows - 1) / 3,
+        ecc_level as usize * 3 + (rows - 1) % 3,
+        columns - 1,
+    ];
+    codewords
+        .chunks(columns)
+        .enumerate()
+        .map(|(i, codewords)| {
+            let cluster = i % 3;
+            let left = 30 * (i / 3) + indicators[cluster];
+            let right = 30 * (i / 3) + indicators[(cluster + 2) % 3];
+
+            let mut modules = Vec::new();
+            push_bits(&mut modules, 0x1FEA8, 17);
+            for &codeword in [left as u16].iter().chain(codewords).chain(&[right as u16]) {
+                push_bits(
+                    &mut modules,
+                    PDF417_CODEWORDS[cluster][codeword as usize] as u32,
+                    16,
+                );
+                modules.push(false);
+            }
+            push_bits(&mut modules, 0x3FA29, 18);
+            row(modules, 3)
+        })
+        .collect()
+}
@@ -0,0 +1,384 @@
+use bit_vec::BitVec;
+
+use super::{
+    gs1_check_digit, DATABAR_CHECKSUM_WEIGHT, DATABAR_FINDER_PATTERN, DATABAR_G_SUM,
+    DATABAR_LIMITED_CHECKSUM_WEIGHT, DATABAR_LIMITED_FINDER_PATTERN, DATABAR_LIMITED_G_SUM,
+    DATABAR_LIMITED_MODULES, DATABAR_LIMITED_T_EVEN, DATABAR_LIMITED_WIDEST, DATABAR_MODULES,
+    DATABAR_T_EVEN_ODD, DATABAR_WIDEST,
+};
+use crate::{EncodingError, StackedRow, Symbol};
+
+/// GS1 DataBar Omnidirectional format, for more [`info`](https://en.wikipedia.org/wiki/GS1_DataBar)
+///
+/// Encodes a GTIN of up to 13 digits, or 14 digits when the check digit is included.
+pub struct DataBar;
+
+/// GS1 DataBar Limited format, for more [`info`](https://en.wikipedia.org/wiki/GS1_DataBar)
+///
+/// Encodes a GTIN of up to 13 digits starting with 0 or 1, or 14 digits when the check digit is
+/// included.
+pub struct DataBarLimited;
+
+/// Layout of a [`DataBar`] symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBarLayout {
+    /// Single row, 33 modules high
+    Omnidirectional,
+    /// Single row, 13 modules high
+    Truncated,
+    /// Two rows, 5 and 7 modules high, with a separator
+    Stacked,
+    /// Two rows, 33 modules high each, with a 3 row separator
+    StackedOmnidirectional,
+}
+
+impl DataBar {
+    /// Encode the provided digits with the given layout, as [`Symbol::Stacked`] rows
+    pub fn encode(layout: DataBarLayout, chars: &[char]) -> Result<Symbol, EncodingError> {
+        Ok(Symbol::Stacked(omni_rows(gtin_value(chars)?, layout)))
+    }
+}
+
+impl DataBarLimited {
+    /// Encode the provided digits, as a single [`Symbol::Stacked`] row
+    pub fn encode(chars: &[char]) -> Result<Symbol, EncodingError> {
+        let value = gtin_value(chars)?;
+        if value >= 2_000_000_000_000 {
+            return Err(EncodingError::WrongChar);
+        }
+        Ok(Symbol::Stacked(vec![limited_row(value)]))
+    }
+}
+
+/// Value of a GTIN without its check digit, the check digit being verified when present
+pub(crate) fn gtin_value(chars: &[char]) -> Result<u64, EncodingError> {
+    if chars.is_empty() || chars.len() > 14 {
+        return Err(EncodingError::WrongSize);
+    }
+    let digits = chars
+        .iter()
+        .map(|&c| c.is_ascii_digit().then_some(c as u8))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(EncodingError::WrongChar)?;
+    let digits = if digits.len() == 14 {
+        if gs1_check_digit(&digits[..13]) != digits[13] {
+            return Err(EncodingError::WrongCheckusm);
+        }
+        &digits[..13]
+    } else {
+        &digits
+    };
+    Ok(digits
+        .iter()
+        .fold(0, |value, &d| value * 10 + (d - b'0') as u64))
+}
+
+/// Number of combinations of `r` selected from `n`, ISO/IEC 24724 Annex B
+fn combins(n: i32, r: i32) -> i32 {
+    let (min_denom, max_denom) = if n - r > r { (r, n - r) } else { (n - r, r) };
+    let mut value = 1;
+    let mut j = 1;
+    for i in (max_denom + 1..=n).rev() {
+        value *= i;
+        if j <= min_denom {
+            value /= j;
+            j += 1;
+        }
+    }
+    while j <= min_denom {
+        value /= j;
+        j += 1;
+    }
+    value
+}
+
+/// Widths of the `elements` elements of `n` modules encoding `value`, ISO/IEC 24724 Annex B
+fn get_widths(
+    mut value: i32,
+    mut n: i32,
+    elements: i32,
+    max_width: i32,
+    no_narrow: bool,
+) -> [u8; 7] {
+    let mut widths = [0; 7];
+    let mut narrow_mask = 0;
+    for bar in 0..elements - 1 {
+        let mut element_width = 1;
+        narrow_mask |= 1 << bar;
+        let mut sub_value;
+        loop {
+            sub_value = combins(n - element_width - 1, elements - bar - 2);
+            if no_narrow
+                && narrow_mask == 0
+                && n - element_width - (elements - bar - 1) >= elements - bar - 1
+            {
+                sub_value -= combins(n - element_width - (elements - bar), elements - bar - 2);
+            }
+            if elements - bar - 1 > 1 {
+                let mut less_value = 0;
+                let mut max_element = n - element_width - (elements - bar - 2);
+                while max_element > max_width {
+                    less_value += combins(n - element_width - max_element - 1, elements - bar - 3);
+                    max_element -= 1;
+                }
+                sub_value -= less_value * (elements - 1 - bar);
+            } else if n - element_width > max_width {
+                sub_value -= 1;
+            }
+            value -= sub_value;
+            if value < 0 {
+                break;
+            }
+            element_width += 1;
+            narrow_mask &= !(1 << bar);
+        }
+        value += sub_value;
+        n -= element_width;
+        widths[bar as usize] = element_width as u8;
+    }
+    widths[elements as usize - 1] = n as u8;
+    widths
+}
+
+/// Interleaved odd and even element widths of a character
+pub(crate) fn character_widths(
+    odd_value: i32,
+    even_value: i32,
+    odd_modules: i32,
+    even_modules: i32,
+    elements: i32,
+    max_width: i32,
+    no_narrow: bool,
+) -> [u8; 14] {
+    let odd = get_widths(odd_value, odd_modules, elements, max_width, no_narrow);
+    let even = get_widths(
+        even_value,
+        even_modules,
+        elements,
+        9 - max_width,
+        !no_narrow,
+    );
+    let mut widths = [0; 14];
+    for i in 0..elements as usize {
+        widths[2 * i] = odd[i];
+        widths[2 * i + 1] = even[i];
+    }
+    widths
+}
+
+/// Append modules of alternating colours of the given widths, starting with a bar if `bar`
+pub(crate) fn expand_widths(modules: &mut Vec<bool>, widths: &[u8], mut bar: bool) {
+    for &width in widths {
+        modules.extend(std::iter::repeat_n(bar, width as usize));
+        bar = !bar;
+    }
+}
+
+/// Group of an Omnidirectional data character, outside characters using groups 0 to 4
+fn omni_group(value: i32, outside: bool) -> usize {
+    let (start, end) = if outside { (0, 4) } else { (5, 8) };
+    (start..end)
+        .find(|&i| value < DATABAR_G_SUM[i + 1] as i32)
+        .unwrap_or(end)
+}
+
+/// Element widths of an Omnidirectional symbol, and the value of its right finder pattern
+pub(crate) fn omni_widths(value: u64) -> ([u8; 46], usize) {
+    let left_pair = (value / 4537077) as i32;
+    let right_pair = (value % 4537077) as i32;
+    let data_characters = [
+        left_pair / 1597,
+        left_pair % 1597,
+        right_pair / 1597,
+        right_pair % 1597,
+    ];
+
+    let mut data_widths = [[0; 14]; 4];
+    for (i, &character) in data_characters.iter().enumerate() {
+        let outside = i % 2 == 0;
+        let group = omni_group(character, outside);
+        let v = character - DATABAR_G_SUM[group] as i32;
+        let t = DATABAR_T_EVEN_ODD[group] as i32;
+        let (odd, even) = if outside {
+            (v / t, v % t)
+        } else {
+            (v % t, v / t)
+        };
+        data_widths[i] = character_widths(
+            odd,
+            even,
+            DATABAR_MODULES[group] as i32,
+            DATABAR_MODULES[group + 9] as i32,
+            4,
+            DATABAR_WIDEST[group] as i32,
+            !outside,
+        );
+    }
+
+    let mut checksum = (0..4)
+        .flat_map(|i| (0..8).map(move |j| (i, j)))
+        .map(|(i, j)| DATABAR_CHECKSUM_WEIGHT[i][j] as usize * data_widths[i][j] as usize)
+        .sum::<usize>()
+        % 79;
+    if checksum >= 8 {
+        checksum += 1;
+    }
+    if checksum >= 72 {
+        checksum += 1;
+    }
+    let (c_left, c_right) = (checksum / 9, checksum % 9);
+
+    let mut widths = [1; 46];
+    for i in 0..8 {
+        widths[i + 2] = data_widths[0][i];
+        widths[i + 15] = data_widths[1][7 - i];
+        widths[i + 23] = data_widths[3][i];
+        widths[i + 36] = data_widths[2][7 - i];
+    }
+    for i in 0..5 {
+        widths[i + 10] = DATABAR_FINDER_PATTERN[c_left][i];
+        widths[i + 31] = DATABAR_FINDER_PATTERN[c_right][4 - i];
+    }
+    (widths, c_right)
+}
+
+/// Separator between an Omnidirectional row and an adjacent row, the complement of the row with
+/// the modules over the finder patterns starting at `finder_starts` alternating, ISO/IEC 24724
+/// 5.3.2.2
+pub(crate) fn omni_separator(
+    row: &[bool],
+    width: usize,
+    finder_starts: &[usize],
+    bottom_finder_value_3: bool,
+) -> Vec<bool> {
+    let mut separator = vec![false; row.len()];
+    for i in 4..width - 4 {
+        separator[i] = !row[i];
+    }
+    for &start in finder_starts {
+        if bottom_finder_value_3 {
+            // the dark module over finder value 3 is shifted over the start of its wide bar
+            for (i, module) in separator[start..start + 13].iter_mut().enumerate() {
+                *module = i == 10;
+            }
+            continue;
+        }
+        let mut latch = true;
+        for i in start..start + 13 {
+            if row[i] {
+                separator[i] = false;
+                latch = true;
+            } else {
+                separator[i] = latch;
+                latch = !latch;
+            }
+        }
+    }
+    separator
+}
+
+fn stacked_row(modules: Vec<bool>, height: usize) -> StackedRow {
+    StackedRow {
+        modules: BitVec::from_iter(modules),
+        height,
+    }
+}
+
+/// Rows of an Omnidirectional family symbol encoding `value`
+pub(crate) fn omni_rows(value: u64, layout: DataBarLayout) -> Vec<StackedRow> {
+    let (widths, c_right) = omni_widths(value);
+
+    let height = match layout {
+        DataBarLayout::Omnidirectional => 33,
+        DataBarLayout::Truncated => 13,
+        DataBarLayout::Stacked | DataBarLayout::StackedOmnidirectional => {
+            let mut top = Vec::with_capacity(50);
+            expand_widths(&mut top, &widths[..23], false);
+            top.extend([true, false]);
+            let mut bottom = vec![true, false];
+            expand_widths(&mut bottom, &widths[23..], true);
+            return stacked_rows(top, bottom, c_right, layout);
+        }
+    };
+    let mut modules = Vec::with_capacity(96);
+    expand_widths(&mut modules, &widths, false);
+    vec![stacked_row(modules, height)]
+}
+
+fn stacked_rows(
+    top: Vec<bool>,
+    bottom: Vec<bool>,
+    c_right: usize,
+    layout: DataBarLayout,
+) -> Vec<StackedRow> {
+    if layout == DataBarLayout::Stacked {
+        let mut separator = vec![false; 50];
+        for i in 1..46 {
+            separator[i] = if top[i] == bottom[i] {
+                !top[i]
+            } else {
+                !separator[i - 1]
+            };
+        }
+        separator[1..4].fill(false);
+        return vec![
+            stacked_row(top, 5),
+            stacked_row(separator, 1),
+            stacked_row(bottom, 7),
+        ];
+    }
+
+    let top_separator = omni_separator(&top, 50, &[18], false);
+    let middle_separator = (0..50)
+        .map(|i| (5..46).contains(&i) && i % 2 == 1)
+        .collect();
+    let bottom_separator = omni_separator(&bottom, 50, &[19], c_right == 3);
+    vec![
+        stacked_row(top, 33),
+        stacked_row(top_separator, 1),
+        stacked_row(middle_separator, 1),
+        stacked_row(bottom_separator, 1),
+        stacked_row(bottom, 33),
+    ]
+}
+
+/// Row of a Limited symbol encoding `value`
+pub(crate) fn limited_row(value: u64) -> StackedRow {
+    let pair_values = [(value / 2013571) as i32, (value % 2013571) as i32];
+    let pair_widths = pair_values.map(|mut v| {
+        let group = (1..7)
+            .rev()
+            .find(|&g| v >= DATABAR_LIMITED_G_SUM[g] as i32)
+            .unwrap_or(0);
+        v -= DATABAR_LIMITED_G_SUM[group] as i32;
+        let t = DATABAR_LIMITED_T_EVEN[group] as i32;
+        let modules = DATABAR_LIMITED_MODULES[group] as i32;
+        character_widths(
+            v / t,
+            v % t,
+            modules,
+            26 - modules,
+            7,
+            DATABAR_LIMITED_WIDEST[group] as i32,
+            false,
+        )
+    });
+
+    let checksum = (0..14)
+        .map(|i| {
+            DATABAR_LIMITED_CHECKSUM_WEIGHT[0][i] as usize * pair_widths[0][i] as usize
+                + DATABAR_LIMITED_CHECKSUM_WEIGHT[1][i] as usize * pair_widths[1][i] as usize
+        })
+        .sum::<usize>()
+        % 89;
+
+    let mut widths = [1; 47];
+    widths[2..16].copy_from_slice(&pair_widths[0]);
+    widths[16..30].copy_from_slice(&DATABAR_LIMITED_FINDER_PATTERN[checksum]);
+    widths[30..44].copy_from_slice(&pair_widths[1]);
+    widths[46] = 5;
+
+    let mut modules = Vec::with_capacity(79);
+    expand_widths(&mut modules, &widths, false);
+    stacked_row(modules, 10)
+}
@@ -0,0 +1,482 @@
+use bit_vec::BitVec;
+
+use super::{
+    character_widths, expand_widths, general_field_encode, gs1_reduce, push_bits, GeneralFieldMode,
+    DATABAR_EXPANDED_CHECKSUM_WEIGHT, DATABAR_EXPANDED_FINDER_PATTERN,
+    DATABAR_EXPANDED_FINDER_SEQUENCE, DATABAR_EXPANDED_G_SUM, DATABAR_EXPANDED_MODULES,
+    DATABAR_EXPANDED_T_EVEN, DATABAR_EXPANDED_WEIGHT_ROWS, DATABAR_EXPANDED_WIDEST,
+};
+use crate::{EncodingError, StackedRow, Symbol};
+
+/// GS1 DataBar Expanded format, for more [`info`](https://en.wikipedia.org/wiki/GS1_DataBar)
+///
+/// Encodes a GS1 element string with its AIs in brackets, as `(01)98898765432106(3202)012345`,
+/// compressing the common combinations of GTIN, weight, price and date.
+pub struct DataBarExpanded;
+
+impl DataBarExpanded {
+    /// Encode the provided element string, as a single [`Symbol::Stacked`] row
+    pub fn encode(chars: &[char]) -> Result<Symbol, EncodingError> {
+        let reduced = gs1_reduce(chars)?;
+        Ok(Symbol::Stacked(expanded_rows(&reduced, None, false)?))
+    }
+
+    /// Encode the provided element string as DataBar Expanded Stacked, with up to
+    /// `segments_per_row` symbol characters per row, an even number from 2 to 22
+    pub fn encode_stacked(
+        segments_per_row: usize,
+        chars: &[char],
+    ) -> Result<Symbol, EncodingError> {
+        if segments_per_row % 2 == 1 || !(2..=22).contains(&segments_per_row) {
+            return Err(EncodingError::WrongSize);
+        }
+        let reduced = gs1_reduce(chars)?;
+        let rows = expanded_rows(&reduced, Some(segments_per_row / 2), false)?;
+        Ok(Symbol::Stacked(rows))
+    }
+}
+
+/// Value of the `digits`, if they are all digits
+fn to_int(digits: &[u8]) -> Option<u32> {
+    digits.iter().try_fold(0, |value, &d| {
+        d.is_ascii_digit().then(|| value * 10 + (d - b'0') as u32)
+    })
+}
+
+/// Compressed value of the YYMMDD date at `position`, ISO/IEC 24724 7.2.5.4.2
+pub(crate) fn date_value(reduced: &[u8], position: usize) -> Option<u32> {
+    let date = reduced.get(position..position + 6)?;
+    let (yy, mm, dd) = (
+        to_int(&date[..2])?,
+        to_int(&date[2..4])?,
+        to_int(&date[4..])?,
+    );
+    // day 0 stands for the last day of the month
+    if mm == 0 || mm > 12 || dd > 31 {
+        return None;
+    }
+    Some(yy * 384 + (mm - 1) * 32 + dd)
+}
+
+/// Encoding method of the reduced element string, ISO/IEC 24724 Table 10
+fn encoding_method(reduced: &[u8]) -> u8 {
+    let len = reduced.len();
+    if len < 16 || !reduced.starts_with(b"01") {
+        return 2;
+    }
+    if len < 20 || reduced[2] != b'9' || reduced[16] != b'3' {
+        return 1;
+    }
+
+    let weight = reduced.get(20..26).and_then(to_int);
+    let date_ai = len == 34
+        && reduced[26] == b'1'
+        && matches!(reduced[27], b'1' | b'3' | b'5' | b'7')
+        && date_value(reduced, 28).is_some();
+    match (&reduced[17..19], weight) {
+        // (01) and (310x), metric weight
+        (b"10", Some(weight)) if len >= 26 && weight <= 99999 => {
+            if len == 26 {
+                if reduced[19] == b'3' && weight <= 32767 {
+                    3
+                } else {
+                    7
+                }
+            } else if date_ai {
+                6 + reduced[27] - b'0'
+            } else {
+                1
+            }
+        }
+        // (01) and (320x), English weight
+        (b"20", Some(weight)) if len >= 26 && weight <= 99999 => {
+            if len == 26 {
+                if (reduced[19] == b'2' && weight <= 9999)
+                    || (reduced[19] == b'3' && weight <= 22767)
+                {
+                    4
+                } else {
+                    8
+                }
+            } else if date_ai {
+                7 + reduced[27] - b'0'
+            } else {
+                1
+            }
+        }
+        // (01) and (392x) price, or (393x) price with ISO 4217 currency
+        (b"92", _) if (b'0'..=b'3').contains(&reduced[19]) => 5,
+        (b"93", _)
+            if (b'0'..=b'3').contains(&reduced[19])
+                && reduced.get(20..23).and_then(to_int).is_some() =>
+        {
+            6
+        }
+        _ => 1,
+    }
+}
+
+/// Number of symbol characters holding `len` bits, plus the check character, avoiding a single
+/// character on the last row of a stacked symbol
+fn symbol_characters(len: usize, characters_per_row: usize) -> usize {
+    let mut characters = len.div_ceil(12) + 1;
+    if characters_per_row > 0 && characters % characters_per_row == 1 {
+        characters += 1;
+    }
+    characters.max(4)
+}
+
+/// Bits of the data characters, linkage flag first, ISO/IEC 24724 7.2.5
+fn binary_string(
+    reduced: &[u8],
+    linkage: bool,
+    characters_per_row: usize,
+) -> Result<Vec<bool>, EncodingError> {
+    if reduced.len() > 77 {
+        return Err(EncodingError::TooLong);
+    }
+
+    let mut bits = vec![linkage];
+    let method = encoding_method(reduced);
+    let read_position = match method {
+        1 => {
+            push_bits(&mut bits, 4, 3);
+            16
+        }
+        2 => {
+            push_bits(&mut bits, 0, 4);
+            0
+        }
+        3 | 4 => {
+            push_bits(&mut bits, 4 + method as u32 - 3, 4);
+            26
+        }
+        5 => {
+            push_bits(&mut bits, 0x30, 7);
+            20
+        }
+        6 => {
+            push_bits(&mut bits, 0x34, 7);
+            23
+        }
+        _ => {
+            push_bits(&mut bits, 56 + method as u32 - 7, 7);
+            reduced.len()
+        }
+    };
+
+    // the compressed data field only holds digits
+    let compressed = &reduced[..read_position];
+    if !compressed.iter().all(u8::is_ascii_digit) {
+        return Err(EncodingError::WrongChar);
+    }
+    let item = |bits: &mut Vec<bool>| {
+        for i in (3..15).step_by(3) {
+            push_bits(bits, to_int(&compressed[i..i + 3]).unwrap_or_default(), 10);
+        }
+    };
+    match method {
+        1 => {
+            push_bits(&mut bits, (compressed[2] - b'0') as u32, 4);
+            item(&mut bits);
+        }
+        3 | 4 => {
+            item(&mut bits);
+            let mut weight = to_int(&compressed[20..26]).unwrap_or_default();
+            if method == 4 && compressed[19] == b'3' {
+                weight += 10000;
+            }
+            push_bits(&mut bits, weight, 15);
+        }
+        5 | 6 => {
+            item(&mut bits);
+            push_bits(&mut bits, (compressed[19] - b'0') as u32, 2);
+            if method == 6 {
+                push_bits(
+                    &mut bits,
+                    to_int(&compressed[20..23]).unwrap_or_default(),
+                    10,
+                );
+            }
+        }
+        7..=14 => {
+            item(&mut bits);
+            let weight = (compressed[19] - b'0') as u32 * 100000
+                + to_int(&compressed[21..26]).unwrap_or_default();
+            push_bits(&mut bits, weight, 20);
+            // 38400 stands for no date
+            push_bits(&mut bits, date_value(compressed, 28).unwrap_or(38400), 16);
+        }
+        _ => {}
+    }
+
+    let mut mode = GeneralFieldMode::Numeric;
+    let last_digit = general_field_encode(&reduced[read_position..], &mut mode, &mut bits)?;
+
+    let mut characters = symbol_characters(bits.len(), characters_per_row);
+    let mut remainder = 12 * (characters - 1) - bits.len();
+    if let Some(digit) = last_digit {
+        let digit = (digit - b'0') as u32;
+        if (4..=6).contains(&remainder) {
+            push_bits(&mut bits, digit + 1, 4);
+        } else {
+            push_bits(&mut bits, digit * 11 + 10 + 8, 7);
+        }
+        characters = symbol_characters(bits.len(), characters_per_row);
+        remainder = (12 * (characters - 1)).saturating_sub(bits.len());
+    }
+    if bits.len() > 252 {
+        return Err(EncodingError::TooLong);
+    }
+
+    let mut padding = remainder as isize;
+    if mode == GeneralFieldMode::Numeric {
+        push_bits(&mut bits, 0, 4);
+        padding -= 4;
+    }
+    while padding > 0 {
+        push_bits(&mut bits, 4, 5);
+        padding -= 5;
+    }
+    bits.truncate(12 * (characters - 1));
+
+    // variable length symbol field
+    let variable_bits = match method {
+        1 => Some(2),
+        2 => Some(3),
+        5 | 6 => Some(6),
+        _ => None,
+    };
+    if let Some(i) = variable_bits {
+        bits[i] = characters % 2 == 1;
+        bits[i + 1] = characters > 14;
+    }
+    Ok(bits)
+}
+
+/// Element widths of a symbol character
+fn expanded_character(value: usize) -> [u8; 8] {
+    let group = (0..4)
+        .find(|&i| value < DATABAR_EXPANDED_G_SUM[i + 1] as usize)
+        .unwrap_or(4);
+    let v = (value - DATABAR_EXPANDED_G_SUM[group] as usize) as i32;
+    let t = DATABAR_EXPANDED_T_EVEN[group] as i32;
+    let modules = DATABAR_EXPANDED_MODULES[group] as i32;
+    let widths = character_widths(
+        v / t,
+        v % t,
+        modules,
+        17 - modules,
+        4,
+        DATABAR_EXPANDED_WIDEST[group] as i32,
+        true,
+    );
+    widths[..8].try_into().unwrap()
+}
+
+/// Element widths of the whole symbol, guards included, and the number of data characters
+fn expanded_elements(bits: &[bool]) -> (Vec<u8>, usize) {
+    let data_characters = bits
+        .chunks(12)
+        .map(|chunk| chunk.iter().fold(0, |value, &b| value << 1 | b as usize))
+        .map(expanded_character)
+        .collect::<Vec<_>>();
+    let data_count = data_characters.len();
+    let symbol_count = data_count + 1;
+
+    let weight_rows = &DATABAR_EXPANDED_WEIGHT_ROWS[(data_count - 2) / 2];
+    let checksum = data_characters
+        .iter()
+        .enumerate()
+        .flat_map(|(i, widths)| {
+            let weights = &DATABAR_EXPANDED_CHECKSUM_WEIGHT[weight_rows[i] as usize];
+            widths
+                .iter()
+                .zip(weights)
+                .map(|(&w, &k)| w as usize * k as usize)
+        })
+        .sum::<usize>();
+    let check_character = expanded_character(211 * (symbol_count - 4) + checksum % 211);
+
+    let codeblocks = symbol_count.div_ceil(2);
+    let pattern_width = codeblocks * 5 + symbol_count * 8 + 4;
+    let mut elements = vec![0; pattern_width];
+    let sequence = &DATABAR_EXPANDED_FINDER_SEQUENCE[(symbol_count - 1) / 2 - 1];
+    for i in 0..codeblocks {
+        let pattern = &DATABAR_EXPANDED_FINDER_PATTERN[sequence[i] as usize - 1];
+        elements[21 * i + 10..21 * i + 15].copy_from_slice(pattern);
+    }
+    elements[2..10].copy_from_slice(&check_character);
+    for (i, widths) in data_characters.iter().enumerate() {
+        // characters left of a finder read right to left
+        if i % 2 == 0 {
+            let k = i / 2 * 21 + 15;
+            for j in 0..8 {
+                elements[k + j] = widths[7 - j];
+            }
+        } else {
+            let k = (i - 1) / 2 * 21 + 23;
+            elements[k..k + 8].copy_from_slice(widths);
+        }
+    }
+    (elements, data_count)
+}
+
+/// Separator between an Expanded row and an adjacent row holding `columns` finder patterns, the
+/// complement of the row with the modules over the finder patterns alternating
+pub(crate) fn expanded_separator(
+    row: &[bool],
+    columns: usize,
+    special_case_row: bool,
+    left_to_right: bool,
+    odd_last_row: bool,
+    v2_latch: &mut bool,
+) -> Vec<bool> {
+    let module = |i: usize| row.get(i).copied().unwrap_or(false);
+    let mut separator = vec![false; row.len()];
+    let offset = special_case_row as usize;
+    for j in 4 + offset..row.len().saturating_sub(4) {
+        separator[j] = !row[j];
+    }
+
+    let mut space_latch = false;
+    let mut adjust = |i: usize, separator: &mut Vec<bool>| {
+        let value = if module(i) {
+            space_latch = false;
+            false
+        } else {
+            space_latch = !space_latch;
+            space_latch
+        };
+        if let Some(m) = separator.get_mut(i) {
+            *m = value;
+        }
+    };
+    for j in 0..columns {
+        let k = 49 * j + 19 + offset;
+        if left_to_right {
+            // last 13 modules of a version 2 finder, first 13 of a version 1 finder
+            let range = if *v2_latch { 2..15 } else { 0..13 };
+            for i in range {
+                adjust(i + k, &mut separator);
+            }
+        } else {
+            // no data character starts a row ending with a finder
+            let k = if odd_last_row { k - 17 } else { k };
+            let range = if *v2_latch { 2..=14 } else { 0..=12 };
+            for i in range.rev() {
+                adjust(i + k, &mut separator);
+            }
+        }
+        *v2_latch = !*v2_latch;
+    }
+    separator
+}
+
+fn stacked_row(modules: Vec<bool>, height: usize) -> StackedRow {
+    StackedRow {
+        modules: BitVec::from_iter(modules),
+        height,
+    }
+}
+
+/// Rows of an Expanded symbol encoding the reduced element string, stacked with `columns`
+/// segment pairs per row if given
+pub(crate) fn expanded_rows(
+    reduced: &[u8],
+    columns: Option<usize>,
+    linkage: bool,
+) -> Result<Vec<StackedRow>, EncodingError> {
+    let bits = binary_string(reduced, linkage, 2 * columns.unwrap_or(0))?;
+    let (mut elements, data_count) = expanded_elements(&bits);
+    let pattern_width = elements.len();
+
+    let Some(columns) = columns else {
+        elements[..2].fill(1);
+        elements[pattern_width - 2..].fill(1);
+        let mut modules = Vec::new();
+        expand_widths(&mut modules, &elements, false);
+        return Ok(vec![stacked_row(modules, 34)]);
+    };
+
+    let codeblocks = (data_count + 1).div_ceil(2);
+    let stack_rows = codeblocks.div_ceil(columns);
+    let mut rows = Vec::with_capacity(4 * stack_rows - 3);
+    let mut v2_latch = false;
+    let mut current_block = 0;
+    for current_row in 1..=stack_rows {
+        let odd_row = current_row % 2 == 1;
+        let num_columns = if current_row * columns > codeblocks {
+            codeblocks - current_block
+        } else {
+            columns
+        };
+        // a partial last row with an odd number of finders keeps the reading direction
+        let special_case_row = current_row == stack_rows
+            && num_columns != columns
+            && !odd_row
+            && columns % 2 == 0
+            && num_columns % 2 == 1;
+        let left_to_right = columns % 2 == 1 || odd_row || special_case_row;
+
+        let mut sub_elements = vec![0; 2 + 21 * num_columns];
+        sub_elements[..2].fill(1);
+        if special_case_row {
+            sub_elements[0] = 2;
+        }
+        let mut reader = 0;
+        loop {
+            let start = 2 + current_block * 21;
+            for j in 0..21 {
+                if let Some(&width) = elements.get(start + j) {
+                    let position = if left_to_right {
+                        j + reader * 21 + 2
+                    } else {
+                        (20 - j) + (num_columns - 1 - reader) * 21 + 2
+                    };
+                    sub_elements[position] = width;
+                }
+            }
+            reader += 1;
+            current_block += 1;
+            if reader >= columns || current_block >= codeblocks {
+                break;
+            }
+        }
+        sub_elements.extend([1, 1]);
+
+        let mut modules = Vec::new();
+        expand_widths(&mut modules, &sub_elements, !(odd_row || special_case_row));
+
+        if current_row != 1 {
+            let odd_last_row = current_row == stack_rows && data_count % 2 == 0;
+            let middle = (0..modules.len().max(49 * columns))
+                .map(|j| j >= 5 && j < 49 * columns && j % 2 == 1)
+                .collect();
+            let bottom = expanded_separator(
+                &modules,
+                reader,
+                special_case_row,
+                left_to_right,
+                odd_last_row,
+                &mut v2_latch.clone(),
+            );
+            rows.push(stacked_row(middle, 1));
+            rows.push(stacked_row(bottom, 1));
+        }
+        let top = (current_row != stack_rows).then(|| {
+            expanded_separator(&modules, reader, false, left_to_right, false, &mut v2_latch)
+        });
+        rows.push(stacked_row(modules, 34));
+        if let Some(top) = top {
+            rows.push(stacked_row(top, 1));
+        }
+    }
+
+    // rows of a partial last row are narrower
+    let width = rows.iter().map(|r| r.modules.len()).max().unwrap_or(0);
+    for row in &mut rows {
+        row.modules.grow(width - row.modules.len(), false);
+    }
+    Ok(rows)
+}
@@ -0,0 +1,247 @@
+/// Lowest value of each group, outside groups first, ISO/IEC 24724 Tables 1 and 2
+pub static DATABAR_G_SUM: [u16; 9] = [0, 161, 961, 2015, 2715, 0, 336, 1036, 1516];
+
+/// Combinations of the even (outside) or odd (inside) elements of each group
+pub static DATABAR_T_EVEN_ODD: [u16; 9] = [1, 10, 34, 70, 126, 4, 20, 48, 81];
+
+/// Modules of the odd elements of each group, followed by those of the even elements
+pub static DATABAR_MODULES: [u8; 18] = [12, 10, 8, 6, 4, 5, 7, 9, 11, 4, 6, 8, 10, 12, 10, 8, 6, 4];
+
+/// Widest odd element of each group, the widest even element being 9 minus it
+pub static DATABAR_WIDEST: [u8; 9] = [8, 6, 4, 3, 1, 2, 4, 6, 8];
+
+/// Finder pattern widths, ISO/IEC 24724 Table 4
+pub static DATABAR_FINDER_PATTERN: [[u8; 5]; 9] = [
+    [3, 8, 2, 1, 1],
+    [3, 5, 5, 1, 1],
+    [3, 3, 7, 1, 1],
+    [3, 1, 9, 1, 1],
+    [2, 7, 4, 1, 1],
+    [2, 5, 6, 1, 1],
+    [2, 3, 8, 1, 1],
+    [1, 5, 7, 1, 1],
+    [1, 3, 9, 1, 1],
+];
+
+/// Element weights of each data character, ISO/IEC 24724 Table 5
+pub static DATABAR_CHECKSUM_WEIGHT: [[u16; 8]; 4] = [
+    [1, 3, 9, 27, 2, 6, 18, 54],
+    [4, 12, 36, 29, 8, 24, 72, 58],
+    [16, 48, 65, 37, 32, 17, 51, 74],
+    [64, 34, 23, 69, 49, 68, 46, 59],
+];
+
+/// Combinations of the even elements of each group, ISO/IEC 24724 Table 6
+pub static DATABAR_LIMITED_T_EVEN: [u16; 7] = [28, 728, 6454, 203, 2408, 1, 16632];
+
+/// Modules of the odd elements of each group, the even elements taking 26 minus them
+pub static DATABAR_LIMITED_MODULES: [u8; 7] = [17, 13, 9, 15, 11, 19, 7];
+
+/// Widest odd element of each group, the widest even element being 9 minus it
+pub static DATABAR_LIMITED_WIDEST: [u8; 7] = [6, 5, 3, 5, 4, 8, 1];
+
+/// Element weights of the two data characters, ISO/IEC 24724 Table 7
+pub static DATABAR_LIMITED_CHECKSUM_WEIGHT: [[u16; 14]; 2] = [
+    [1, 3, 9, 27, 81, 65, 17, 51, 64, 14, 42, 37, 22, 66],
+    [20, 60, 2, 6, 18, 54, 73, 41, 34, 13, 39, 28, 84, 74],
+];
+
+/// Check character patterns, ISO/IEC 24724 Annex C
+pub static DATABAR_LIMITED_FINDER_PATTERN: [[u8; 14]; 89] = [
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 3, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 3, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 3, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 3, 1, 1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 2, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 3, 1, 1, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 2, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 2, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 1, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 2, 2, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 2, 2, 1, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 3, 1, 1, 2, 1, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1, 1],
+    [1, 1, 2, 2, 1, 1, 1, 1, 1, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 2, 1, 2, 1, 1, 2, 1, 1, 1],
+    [2, 1, 1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1, 1],
+];
+
+/// Lowest value of each group, ISO/IEC 24724 Table 8
+pub static DATABAR_EXPANDED_G_SUM: [u16; 5] = [0, 348, 1388, 2948, 3988];
+
+/// Combinations of the even elements of each group
+pub static DATABAR_EXPANDED_T_EVEN: [u16; 5] = [4, 20, 52, 104, 204];
+
+/// Modules of the odd elements of each group, the even elements taking 17 minus them
+pub static DATABAR_EXPANDED_MODULES: [u8; 5] = [12, 10, 8, 6, 4];
+
+/// Widest odd element of each group, the widest even element being 9 minus it
+pub static DATABAR_EXPANDED_WIDEST: [u8; 5] = [7, 5, 4, 3, 1];
+
+/// Element weights of each character position, ISO/IEC 24724 Table 14
+pub static DATABAR_EXPANDED_CHECKSUM_WEIGHT: [[u8; 8]; 23] = [
+    [1, 3, 9, 27, 81, 32, 96, 77],
+    [20, 60, 180, 118, 143, 7, 21, 63],
+    [189, 145, 13, 39, 117, 140, 209, 205],
+    [193, 157, 49, 147, 19, 57, 171, 91],
+    [62, 186, 136, 197, 169, 85, 44, 132],
+    [185, 133, 188, 142, 4, 12, 36, 108],
+    [113, 128, 173, 97, 80, 29, 87, 50],
+    [150, 28, 84, 41, 123, 158, 52, 156],
+    [46, 138, 203, 187, 139, 206, 196, 166],
+    [76, 17, 51, 153, 37, 111, 122, 155],
+    [43, 129, 176, 106, 107, 110, 119, 146],
+    [16, 48, 144, 10, 30, 90, 59, 177],
+    [109, 116, 137, 200, 178, 112, 125, 164],
+    [70, 210, 208, 202, 184, 130, 179, 115],
+    [134, 191, 151, 31, 93, 68, 204, 190],
+    [148, 22, 66, 198, 172, 94, 71, 2],
+    [6, 18, 54, 162, 64, 192, 154, 40],
+    [120, 149, 25, 75, 14, 42, 126, 167],
+    [79, 26, 78, 23, 69, 207, 199, 175],
+    [103, 98, 83, 38, 114, 131, 182, 124],
+    [161, 61, 183, 127, 170, 88, 53, 159],
+    [55, 165, 73, 8, 24, 72, 5, 15],
+    [45, 135, 194, 160, 58, 174, 100, 89],
+];
+
+/// Finder pattern widths, A1 to F2, ISO/IEC 24724 Table 15
+pub static DATABAR_EXPANDED_FINDER_PATTERN: [[u8; 5]; 12] = [
+    [1, 8, 4, 1, 1],
+    [1, 1, 4, 8, 1],
+    [3, 6, 4, 1, 1],
+    [1, 1, 4, 6, 3],
+    [3, 4, 6, 1, 1],
+    [1, 1, 6, 4, 3],
+    [3, 2, 8, 1, 1],
+    [1, 1, 8, 2, 3],
+    [2, 6, 5, 1, 1],
+    [1, 1, 5, 6, 2],
+    [2, 2, 9, 1, 1],
+    [1, 1, 9, 2, 2],
+];
+
+/// Finder patterns used by each symbol length, ISO/IEC 24724 Table 16
+pub static DATABAR_EXPANDED_FINDER_SEQUENCE: [[u8; 11]; 10] = [
+    [1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [1, 4, 3, 0, 0, 0, 0, 0, 0, 0, 0],
+    [1, 6, 3, 8, 0, 0, 0, 0, 0, 0, 0],
+    [1, 10, 3, 8, 5, 0, 0, 0, 0, 0, 0],
+    [1, 10, 3, 8, 7, 12, 0, 0, 0, 0, 0],
+    [1, 10, 3, 8, 9, 12, 11, 0, 0, 0, 0],
+    [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0],
+    [1, 2, 3, 4, 5, 6, 7, 10, 9, 0, 0],
+    [1, 2, 3, 4, 5, 6, 7, 10, 11, 12, 0],
+    [1, 2, 3, 4, 5, 8, 7, 10, 9, 12, 11],
+];
+
+/// Checksum weight row of each data character for each finder sequence
+pub static DATABAR_EXPANDED_WEIGHT_ROWS: [[u8; 21]; 10] = [
+    [
+        0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 5, 6, 3, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 9, 10, 3, 4, 13, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 17, 18, 3, 4, 13, 14, 7, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 17, 18, 3, 4, 13, 14, 11, 12, 21, 22, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 17, 18, 3, 4, 13, 14, 15, 16, 21, 22, 19, 20, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 0, 0, 0, 0, 0, 0,
+    ],
+    [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 17, 18, 15, 16, 0, 0, 0, 0,
+    ],
+    [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 17, 18, 19, 20, 21, 22, 0, 0,
+    ],
+    [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 13, 14, 11, 12, 17, 18, 15, 16, 21, 22, 19, 20,
+    ],
+];
+
+/// Lowest value of each group, ISO/IEC 24724 Table 6
+pub static DATABAR_LIMITED_G_SUM: [u32; 7] =
+    [0, 183064, 820064, 1000776, 1491021, 1979845, 1996939];
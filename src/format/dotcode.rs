@@ -0,0 +1,670 @@
+use crate::EncodingError;
+
+/// DotCode format, for more [`info`](https://en.wikipedia.org/wiki/DotCode)
+///
+/// Data is encoded with Code Sets A, B, C and binary mode, supporting chars up to `U+00FF`.
+pub struct DotCode;
+
+/// Encoded DotCode symbol.
+///
+/// Dots may only be printed where the sum of the coordinates is even, every other position is
+/// always empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotCodeSymbol {
+    width: usize,
+    height: usize,
+    dots: Vec<bool>,
+}
+
+const GF: usize = 113;
+
+const LATCH_A_FROM_C: u8 = 101;
+const SHIFT_A: u8 = 101;
+const LATCH_A: u8 = 102;
+const LATCH_B_FROM_A: u8 = 102;
+const LATCH_B: u8 = 106;
+const LATCH_C: u8 = 106;
+const FNC1: u8 = 107;
+const BINARY_LATCH: u8 = 112;
+const BINARY_LATCH_A: u8 = 109;
+const BINARY_LATCH_B: u8 = 110;
+const BINARY_LATCH_C: u8 = 111;
+const UPPER_SHIFT_A: u8 = 110;
+const UPPER_SHIFT_B: u8 = 111;
+const PAD: u8 = 106;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CodeSet {
+    A,
+    B,
+    C,
+    Binary,
+}
+
+/// Symbol character dot patterns, AIM DotCode Annex C
+#[rustfmt::skip]
+const DOT_PATTERNS: [u16; 113] = [
+    0x155, 0x0ab, 0x0ad, 0x0b5, 0x0d5, 0x156, 0x15a, 0x16a, 0x1aa, 0x0ae,
+    0x0b6, 0x0ba, 0x0d6, 0x0da, 0x0ea, 0x12b, 0x12d, 0x135, 0x14b, 0x14d,
+    0x153, 0x159, 0x165, 0x169, 0x195, 0x1a5, 0x1a9, 0x057, 0x05b, 0x05d,
+    0x06b, 0x06d, 0x075, 0x097, 0x09b, 0x09d, 0x0a7, 0x0b3, 0x0b9, 0x0cb,
+    0x0cd, 0x0d3, 0x0d9, 0x0e5, 0x0e9, 0x12e, 0x136, 0x13a, 0x14e, 0x15c,
+    0x166, 0x16c, 0x172, 0x174, 0x196, 0x19a, 0x1a6, 0x1ac, 0x1b2, 0x1b4,
+    0x1ca, 0x1d2, 0x1d4, 0x05e, 0x06e, 0x076, 0x07a, 0x09e, 0x0bc, 0x0ce,
+    0x0dc, 0x0e6, 0x0ec, 0x0f2, 0x0f4, 0x117, 0x11b, 0x11d, 0x127, 0x133,
+    0x139, 0x147, 0x163, 0x171, 0x18b, 0x18d, 0x193, 0x199, 0x1a3, 0x1b1,
+    0x1c5, 0x1c9, 0x1d1, 0x02f, 0x037, 0x03b, 0x03d, 0x04f, 0x067, 0x073,
+    0x079, 0x08f, 0x0c7, 0x0e3, 0x0f1, 0x11e, 0x13c, 0x178, 0x18e, 0x19c,
+    0x1b8, 0x1c6, 0x1cc,
+];
+
+impl DotCode {
+    /// Encode the provided chars, choosing the symbol size automatically
+    pub fn encode(chars: &[char]) -> Result<DotCodeSymbol, EncodingError> {
+        let (mut codewords, binary_finish) = encode_text(chars)?;
+
+        let min_dots = 9 * (codewords.len() + 3 + codewords.len() / 2) + 2;
+        let (width, height) = symbol_size(min_dots);
+        if width > 200 || height > 200 {
+            return Err(EncodingError::TooLong);
+        }
+        let n_dots = width * height / 2;
+
+        let mut padding_dots = n_dots - min_dots;
+        let mut first_pad = true;
+        while padding_dots >= 9 {
+            if codewords.len() % 2 == 0 {
+                padding_dots -= 9;
+            } else if padding_dots >= 18 {
+                padding_dots -= 18;
+            } else {
+                break;
+            }
+            // the first pad also terminates a trailing binary mode
+            if binary_finish && first_pad {
+                codewords.push(BINARY_LATCH_A);
+            } else {
+                codewords.push(PAD);
+            }
+            first_pad = false;
+        }
+        let ecc_len = 3 + codewords.len() / 2;
+
+        let mut best = None;
+        for force_corners in [false, true] {
+            for mask in 0..4 {
+                let dots = place(&codewords, mask, ecc_len, width, height, force_corners);
+                let score = score(&dots, width, height);
+                if best.as_ref().is_none_or(|(s, _)| score >= *s) {
+                    best = Some((score, dots));
+                }
+            }
+            // corners are only forced on when no plain mask scores well enough
+            if best.as_ref().unwrap().0 > (width * height / 2) as i64 {
+                break;
+            }
+        }
+
+        Ok(DotCodeSymbol {
+            width,
+            height,
+            dots: best.unwrap().1,
+        })
+    }
+}
+
+fn is_digit(data: &[u8], i: usize) -> bool {
+    data.get(i).is_some_and(u8::is_ascii_digit)
+}
+
+fn pair(data: &[u8], i: usize) -> u8 {
+    (data[i] - b'0') * 10 + data[i + 1] - b'0'
+}
+
+/// Whether the byte at `i` is directly encodable in Code Set A
+fn datum_a(data: &[u8], i: usize) -> bool {
+    data.get(i).is_some_and(|&b| b <= 95)
+}
+
+/// How many bytes the Code Set B character at `i` takes, 0 when not encodable
+fn datum_b(data: &[u8], i: usize) -> usize {
+    match data.get(i) {
+        Some(32..=127 | 9 | 28 | 29 | 30) => 1,
+        Some(13) if data.get(i + 1) == Some(&10) => 2,
+        _ => 0,
+    }
+}
+
+fn datum_c(data: &[u8], i: usize) -> bool {
+    is_digit(data, i) && is_digit(data, i + 1)
+}
+
+fn binary(data: &[u8], i: usize) -> bool {
+    data.get(i).is_some_and(|&b| b >= 128)
+}
+
+/// Code Set A value of a directly encodable byte
+fn value_a(b: u8) -> u8 {
+    if b < 32 {
+        b + 64
+    } else {
+        b - 32
+    }
+}
+
+/// Code Set B value of the character at `i`, and how many bytes it takes
+fn value_b(data: &[u8], i: usize) -> (u8, usize) {
+    match data[i] {
+        13 => (96, 2),
+        9 => (97, 1),
+        28 => (98, 1),
+        29 => (99, 1),
+        30 => (100, 1),
+        b => (b - 32, 1),
+    }
+}
+
+/// Upper shift of a byte in the range 128 to 255
+fn upper_shift(b: u8) -> [u8; 2] {
+    if b - 128 < 32 {
+        [UPPER_SHIFT_A, b - 128 + 64]
+    } else {
+        [UPPER_SHIFT_B, b - 128 - 32]
+    }
+}
+
+/// Digits "17" followed by 6 digits and "10", encoded with a single codeword prefix
+fn seventeen_ten(data: &[u8], i: usize) -> bool {
+    data.get(i..i + 2) == Some(b"17")
+        && data.get(i + 8..i + 10) == Some(b"10")
+        && (i + 2..i + 8).all(|j| is_digit(data, j))
+}
+
+/// Number of digit pairs starting at `i`
+fn ahead_c(data: &[u8], i: usize) -> usize {
+    (i..data.len())
+        .step_by(2)
+        .take_while(|&j| datum_c(data, j))
+        .count()
+}
+
+/// Number of digit pairs starting at `i`, when starting at `i + 1` would yield fewer
+fn try_c(data: &[u8], i: usize) -> usize {
+    if is_digit(data, i) && ahead_c(data, i) > ahead_c(data, i + 1) {
+        ahead_c(data, i)
+    } else {
+        0
+    }
+}
+
+fn ahead_a(data: &[u8], i: usize) -> usize {
+    (i..data.len())
+        .take_while(|&j| datum_a(data, j) && try_c(data, j) < 2)
+        .count()
+}
+
+/// Bytes and characters encodable in Code Set B starting at `i`
+fn ahead_b(data: &[u8], i: usize) -> (usize, usize) {
+    let mut j = i;
+    let mut count = 0;
+    while datum_b(data, j) > 0 && try_c(data, j) < 2 {
+        j += datum_b(data, j);
+        count += 1;
+    }
+    (j - i, count)
+}
+
+/// Bytes collected in binary mode, up to 5 base 259 values emitted as 6 base 103 codewords
+#[derive(Default)]
+struct BinaryBuffer {
+    value: u64,
+    len: usize,
+}
+
+impl BinaryBuffer {
+    fn push(&mut self, ret: &mut Vec<u8>, b: u8) {
+        self.value = self.value * 259 + b as u64;
+        self.len += 1;
+        if self.len == 5 {
+            self.flush(ret);
+        }
+    }
+
+    fn flush(&mut self, ret: &mut Vec<u8>) {
+        if self.len > 0 {
+            let start = ret.len();
+            for _ in 0..=self.len {
+                ret.push((self.value % 103) as u8);
+                self.value /= 103;
+            }
+            ret[start..].reverse();
+        }
+        *self = Self::default();
+    }
+}
+
+/// Push `n` shifted Code Set B characters starting at `*i`
+fn push_b(ret: &mut Vec<u8>, data: &[u8], i: &mut usize, n: usize) {
+    for _ in 0..n {
+        let (value, len) = value_b(data, *i);
+        ret.push(value);
+        *i += len;
+    }
+}
+
+/// Push `n` digit pairs starting at `*i`
+fn push_c(ret: &mut Vec<u8>, data: &[u8], i: &mut usize, n: usize) {
+    for _ in 0..n {
+        ret.push(pair(data, *i));
+        *i += 2;
+    }
+}
+
+/// Data codewords following the encodation rules of AIM DotCode Annex F, and whether the
+/// message ends in binary mode
+fn encode_text(chars: &[char]) -> Result<(Vec<u8>, bool), EncodingError> {
+    let data = chars
+        .iter()
+        .map(|&c| u8::try_from(c).map_err(|_| EncodingError::WrongChar))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut ret = Vec::new();
+    let mut set = CodeSet::C;
+    let mut buffer = BinaryBuffer::default();
+    let mut i = 0;
+
+    if data.len() > 2 && datum_c(&data, 0) {
+        ret.push(FNC1);
+    } else if let Some(&b @ (9 | 28 | 29 | 30)) = data.first() {
+        // a leading special character would be read as a macro
+        ret.extend([LATCH_A_FROM_C, b + 64]);
+        set = CodeSet::A;
+        i = 1;
+    }
+
+    while i < data.len() {
+        match set {
+            CodeSet::C => {
+                if seventeen_ten(&data, i) {
+                    ret.push(100);
+                    i += 2;
+                    push_c(&mut ret, &data, &mut i, 3);
+                    i += 2;
+                } else if datum_c(&data, i) {
+                    push_c(&mut ret, &data, &mut i, 1);
+                } else if binary(&data, i) {
+                    if is_digit(&data, i + 1) {
+                        ret.extend(upper_shift(data[i]));
+                        i += 1;
+                    } else {
+                        ret.push(BINARY_LATCH);
+                        set = CodeSet::Binary;
+                    }
+                } else {
+                    let (n, nx) = ahead_b(&data, i);
+                    if ahead_a(&data, i) > n {
+                        ret.push(LATCH_A_FROM_C);
+                        set = CodeSet::A;
+                    } else if (1..=4).contains(&nx) {
+                        ret.push(101 + nx as u8);
+                        push_b(&mut ret, &data, &mut i, nx);
+                    } else {
+                        ret.push(LATCH_B);
+                        set = CodeSet::B;
+                    }
+                }
+            }
+            CodeSet::A | CodeSet::B => {
+                let n = try_c(&data, i);
+                let direct = if set == CodeSet::A {
+                    datum_a(&data, i)
+                } else {
+                    datum_b(&data, i) > 0
+                };
+                if n >= 2 {
+                    if n <= 4 {
+                        ret.push(103 + n as u8 - 2);
+                        push_c(&mut ret, &data, &mut i, n);
+                    } else {
+                        ret.push(LATCH_C);
+                        set = CodeSet::C;
+                    }
+                } else if direct && set == CodeSet::A {
+                    ret.push(value_a(data[i]));
+                    i += 1;
+                } else if direct {
+                    push_b(&mut ret, &data, &mut i, 1);
+                } else if binary(&data, i) {
+                    let next_direct = if set == CodeSet::A {
+                        datum_a(&data, i + 1)
+                    } else {
+                        datum_b(&data, i + 1) > 0
+                    };
+                    if next_direct {
+                        ret.extend(upper_shift(data[i]));
+                        i += 1;
+                    } else {
+                        ret.push(BINARY_LATCH);
+                        set = CodeSet::Binary;
+                    }
+                } else if set == CodeSet::A {
+                    let (_, nx) = ahead_b(&data, i);
+                    if (1..=6).contains(&nx) {
+                        ret.push(95 + nx as u8);
+                        push_b(&mut ret, &data, &mut i, nx);
+                    } else {
+                        ret.push(LATCH_B_FROM_A);
+                        set = CodeSet::B;
+                    }
+                } else if ahead_a(&data, i) == 1 {
+                    ret.extend([SHIFT_A, value_a(data[i])]);
+                    i += 1;
+                } else {
+                    ret.push(LATCH_A);
+                    set = CodeSet::A;
+                }
+            }
+            CodeSet::Binary => {
+                let n = try_c(&data, i);
+                if n >= 2 {
+                    buffer.flush(&mut ret);
+                    if n <= 7 {
+                        ret.push(101 + n as u8);
+                        push_c(&mut ret, &data, &mut i, n);
+                    } else {
+                        ret.push(BINARY_LATCH_C);
+                        set = CodeSet::C;
+                    }
+                } else if (i..i + 4).any(|j| binary(&data, j)) {
+                    buffer.push(&mut ret, data[i]);
+                    i += 1;
+                } else {
+                    buffer.flush(&mut ret);
+                    if ahead_a(&data, i) > ahead_b(&data, i).0 {
+                        ret.push(BINARY_LATCH_A);
+                        set = CodeSet::A;
+                    } else {
+                        ret.push(BINARY_LATCH_B);
+                        set = CodeSet::B;
+                    }
+                }
+            }
+        }
+    }
+    buffer.flush(&mut ret);
+    Ok((ret, set == CodeSet::Binary))
+}
+
+/// Smallest symbol holding `min_dots` with a width to height ratio close to 3:2
+fn symbol_size(min_dots: usize) -> (usize, usize) {
+    let min_area = min_dots * 2;
+    let h = (min_area as f64 * 0.666).sqrt();
+    let w = (min_area as f64 * 1.5).sqrt();
+    let mut height = h as usize;
+    let mut width = w as usize;
+
+    // width + height must be odd
+    if (width + height) % 2 == 1 {
+        if width * height < min_area {
+            width += 1;
+            height += 1;
+        }
+    } else if h * (width as f64) < w * (height as f64) {
+        width += 1;
+        if width * height < min_area {
+            width -= 1;
+            height += 1;
+            if width * height < min_area {
+                width += 2;
+            }
+        }
+    } else {
+        height += 1;
+        if width * height < min_area {
+            width += 1;
+            height -= 1;
+            if width * height < min_area {
+                height += 2;
+            }
+        }
+    }
+    (width, height)
+}
+
+/// Append `nc` Reed–Solomon check codewords over GF(113) to `codewords`, split in interleaved
+/// blocks when the symbol holds more codewords than the field size
+fn add_ecc(codewords: &mut Vec<u8>, nc: usize) {
+    let nd = codewords.len();
+    let nw = nd + nc;
+    codewords.resize(nw, 0);
+
+    let mut root = [1usize; GF];
+    for i in 1..GF {
+        root[i] = (3 * root[i - 1]) % GF;
+    }
+
+    let step = (nw + GF - 2) / (GF - 1);
+    for start in 0..step {
+        let block_nd = (nd - start).div_ceil(step);
+        let block_nw = (nw - start).div_ceil(step);
+        let block_nc = block_nw - block_nd;
+
+        let mut c = vec![0usize; block_nc + 1];
+        c[0] = 1;
+        for r in root.iter().skip(1).take(block_nc) {
+            for j in (1..=block_nc).rev() {
+                c[j] = (GF + c[j] - (r * c[j - 1]) % GF) % GF;
+            }
+        }
+
+        let mut e = vec![0usize; block_nc];
+        for i in 0..block_nd {
+            let k = (codewords[start + i * step] as usize + e[0]) % GF;
+            for j in 0..block_nc - 1 {
+                e[j] = (GF - (c[j + 1] * k) % GF + e[j + 1]) % GF;
+            }
+            e[block_nc - 1] = (GF - (c[block_nc] * k) % GF) % GF;
+        }
+        for (i, e) in e.into_iter().enumerate() {
+            codewords[start + (block_nd + i) * step] = ((GF - e) % GF) as u8;
+        }
+    }
+}
+
+fn is_corner(x: usize, y: usize, width: usize, height: usize) -> bool {
+    let corners = if height % 2 == 1 {
+        [
+            (0, 0),
+            (width - 2, 0),
+            (width - 1, 1),
+            (0, height - 1),
+            (width - 2, height - 1),
+            (width - 1, height - 2),
+        ]
+    } else {
+        [
+            (0, 0),
+            (width - 1, 0),
+            (0, height - 2),
+            (1, height - 1),
+            (width - 2, height - 1),
+            (width - 1, height - 2),
+        ]
+    };
+    corners.contains(&(x, y))
+}
+
+/// Mask, protect and lay out the codewords
+fn place(
+    codewords: &[u8],
+    mask: u8,
+    ecc_len: usize,
+    width: usize,
+    height: usize,
+    force_corners: bool,
+) -> Vec<bool> {
+    let weight = [0, 3, 7, 17][mask as usize];
+    let mut masked = Vec::with_capacity(codewords.len() + ecc_len + 1);
+    masked.push(mask);
+    masked.extend(
+        codewords
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| ((c as usize + i * weight) % GF) as u8),
+    );
+    add_ecc(&mut masked, ecc_len);
+
+    let n_dots = width * height / 2;
+    let mut stream = Vec::with_capacity(n_dots);
+    stream.extend([mask & 2 != 0, mask & 1 != 0]);
+    for &c in &masked[1..] {
+        let pattern = DOT_PATTERNS[c as usize];
+        stream.extend((0..9).rev().map(|b| pattern & (1 << b) != 0));
+    }
+    stream.resize(n_dots, true);
+
+    let mut dots = vec![false; width * height];
+    let mut stream = stream.into_iter();
+    let corners = if height % 2 == 1 {
+        // horizontal folding, starting from the bottom row
+        for y in 0..height {
+            for x in (0..width).filter(|x| (x + y) % 2 == 0) {
+                if !is_corner(x, y, width, height) {
+                    dots[(height - y - 1) * width + x] = stream.next().unwrap();
+                }
+            }
+        }
+        [
+            (width - 2, 0),
+            (width - 2, height - 1),
+            (width - 1, 1),
+            (width - 1, height - 2),
+            (0, 0),
+            (0, height - 1),
+        ]
+    } else {
+        // vertical folding
+        for x in 0..width {
+            for y in (0..height).filter(|y| (x + y) % 2 == 0) {
+                if !is_corner(x, y, width, height) {
+                    dots[y * width + x] = stream.next().unwrap();
+                }
+            }
+        }
+        [
+            (width - 1, height - 2),
+            (0, height - 2),
+            (width - 2, height - 1),
+            (1, height - 1),
+            (width - 1, 0),
+            (0, 0),
+        ]
+    };
+    for (x, y) in corners {
+        dots[y * width + x] = force_corners || stream.next().unwrap();
+    }
+    dots
+}
+
+/// Mask scoring, AIM DotCode Annex A
+fn score(dots: &[bool], width: usize, height: usize) -> i64 {
+    let get = |x: i64, y: i64| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < width
+            && (y as usize) < height
+            && dots[y as usize * width + x as usize]
+    };
+    let (w, h) = (width as i64, height as i64);
+
+    // runs of empty interior rows and columns weigh N^n, N being the positions in a row or column
+    let empty_penalty = |lines: i64, positions: i64, empty: &dyn Fn(i64) -> bool| {
+        let mut penalty = 0;
+        let mut local = 0;
+        for line in 1..lines - 1 {
+            if empty(line) {
+                local = if local == 0 {
+                    positions
+                } else {
+                    local * positions
+                };
+            } else {
+                penalty += local;
+                local = 0;
+            }
+        }
+        penalty + local
+    };
+    let penalty = empty_penalty(h, w, &|y| (y & 1..w).step_by(2).all(|x| !get(x, y)))
+        + empty_penalty(w, h, &|x| (x & 1..h).step_by(2).all(|y| !get(x, y)));
+
+    // printed dots along each edge and their extent, the worst edge counts
+    let edge = |positions: Vec<(i64, i64)>, along_x: bool, weight: i64| {
+        let printed = positions
+            .into_iter()
+            .filter(|&(x, y)| get(x, y))
+            .map(|(x, y)| if along_x { x } else { y })
+            .collect::<Vec<_>>();
+        match (printed.first(), printed.last()) {
+            (Some(first), Some(last)) => Some((printed.len() as i64 + last - first) * weight),
+            _ => None,
+        }
+    };
+    let edges = [
+        edge((0..w).step_by(2).map(|x| (x, 0)).collect(), true, h),
+        edge((w & 1..w).step_by(2).map(|x| (x, h - 1)).collect(), true, h),
+        edge((0..h).step_by(2).map(|y| (0, y)).collect(), false, w),
+        edge(
+            (h & 1..h).step_by(2).map(|y| (w - 1, y)).collect(),
+            false,
+            w,
+        ),
+    ];
+    let mut worst_edge = i64::MAX;
+    for edge in edges {
+        match edge {
+            Some(e) => worst_edge = worst_edge.min(e),
+            None => return -99999,
+        }
+    }
+
+    // empty crosses, and printed dots without any printed neighbour
+    let mut isolated = 0;
+    for y in 0..h {
+        for x in (y & 1..w).step_by(2) {
+            if !get(x - 1, y - 1)
+                && !get(x + 1, y - 1)
+                && !get(x - 1, y + 1)
+                && !get(x + 1, y + 1)
+                && (!get(x, y)
+                    || (!get(x - 2, y) && !get(x, y - 2) && !get(x + 2, y) && !get(x, y + 2)))
+            {
+                isolated += 1;
+            }
+        }
+    }
+
+    worst_edge - isolated * isolated - penalty
+}
+
+impl DotCodeSymbol {
+    /// Number of dot columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of dot rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the dot at column `x` of row `y` is printed, `false` outside of the symbol
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.dots[y * self.width + x]
+    }
+
+    /// Whether a dot may be printed at column `x` of row `y`
+    pub fn is_dot_position(x: usize, y: usize) -> bool {
+        (x + y).is_multiple_of(2)
+    }
+}
@@ -2,13 +2,29 @@ use std::println;
 
 use bit_vec::BitVec;
 
-use crate::EncodingError;
+use crate::{Elements, EncodingError};
 
 use super::{calc_checksum, Parity, ParityTable, StaticBarcodeEncoding};
 
 /// EAN13 format, for more [`info`](https://web.archive.org/web/20070202111554/http://www.barcodeisland.com/ean13.phtml)
 pub struct EAN13;
 
+/// Modules of the guard patterns and characters
+const GROUPS: [usize; 15] = [3, 7, 7, 7, 7, 7, 7, 5, 7, 7, 7, 7, 7, 7, 3];
+
+impl EAN13 {
+    /// Encode 12 digits, or 13 with the check digit, as bars and spaces grouped by character,
+    /// the guard patterns included
+    pub fn elements(chars: &[char]) -> Result<Elements, EncodingError> {
+        let modules = match chars.len() {
+            12 => <EAN13 as StaticBarcodeEncoding<12>>::encode(chars.try_into()?)?,
+            13 => <EAN13 as StaticBarcodeEncoding<13>>::encode(chars.try_into()?)?,
+            _ => return Err(EncodingError::WrongSize),
+        };
+        Ok(Elements::grouped(&modules, &GROUPS))
+    }
+}
+
 impl StaticBarcodeEncoding<12> for EAN13 {
     fn encode(chars: &[char; 12]) -> Result<BitVec, EncodingError> {
         let first_char = chars[0];
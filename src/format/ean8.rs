@@ -2,7 +2,7 @@ use bit_vec::BitVec;
 
 use crate::{
     format::{Parity, ParityTable},
-    EncodingError,
+    Elements, EncodingError,
 };
 
 use super::{calc_checksum, StaticBarcodeEncoding};
@@ -10,6 +10,22 @@ use super::{calc_checksum, StaticBarcodeEncoding};
 /// EAN8 format, for more [`info`](https://web.archive.org/web/20070202060730/http://www.barcodeisland.com/ean8.phtml)
 pub struct EAN8;
 
+/// Modules of the guard patterns and characters
+const GROUPS: [usize; 11] = [3, 7, 7, 7, 7, 5, 7, 7, 7, 7, 3];
+
+impl EAN8 {
+    /// Encode 7 digits, or 8 with the check digit, as bars and spaces grouped by character,
+    /// the guard patterns included
+    pub fn elements(chars: &[char]) -> Result<Elements, EncodingError> {
+        let modules = match chars.len() {
+            7 => <EAN8 as StaticBarcodeEncoding<7>>::encode(chars.try_into()?)?,
+            8 => <EAN8 as StaticBarcodeEncoding<8>>::encode(chars.try_into()?)?,
+            _ => return Err(EncodingError::WrongSize),
+        };
+        Ok(Elements::grouped(&modules, &GROUPS))
+    }
+}
+
 impl StaticBarcodeEncoding<7> for EAN8 {
     fn encode(chars: &[char; 7]) -> Result<bit_vec::BitVec, crate::EncodingError> {
         let checksum = calc_checksum::<7>(chars);
@@ -0,0 +1,77 @@
+use bit_vec::BitVec;
+
+use super::Parity;
+use crate::{Elements, EncodingError};
+
+/// EAN and UPC 2 or 5 digit add-on, printed right of the main symbol
+pub struct EanAddOn;
+
+/// Parity of the digits of the 2 digit add-on, by value modulo 4
+const PARITIES_2: [[Parity; 2]; 4] = [
+    [Parity::Odd, Parity::Odd],
+    [Parity::Odd, Parity::Even],
+    [Parity::Even, Parity::Odd],
+    [Parity::Even, Parity::Even],
+];
+
+/// Parity of the digits of the 5 digit add-on, by check digit
+const PARITIES_5: [[Parity; 5]; 10] = {
+    use Parity::{Even as E, Odd as O};
+    [
+        [E, E, O, O, O],
+        [E, O, E, O, O],
+        [E, O, O, E, O],
+        [E, O, O, O, E],
+        [O, E, E, O, O],
+        [O, O, E, E, O],
+        [O, O, O, E, E],
+        [O, E, O, E, O],
+        [O, E, O, O, E],
+        [O, O, E, O, E],
+    ]
+};
+
+impl EanAddOn {
+    /// Light modules between the main symbol and the add-on
+    pub const GAP: usize = 7;
+
+    /// Light modules required right of the add-on
+    pub const QUIET_ZONE: usize = 5;
+
+    /// Encode 2 or 5 digits as the modules of the add-on alone, the parity of its digits
+    /// standing for its value or its check digit
+    pub fn encode(data: &[char]) -> Result<BitVec, EncodingError> {
+        let digits = data
+            .iter()
+            .map(|c| c.to_digit(10).map(|d| d as usize))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(EncodingError::WrongChar)?;
+        let parities: &[Parity] = match digits[..] {
+            [a, b] => &PARITIES_2[(a * 10 + b) % 4],
+            [a, b, c, d, e] => &PARITIES_5[(3 * (a + c + e) + 9 * (b + d)) % 10],
+            _ => return Err(EncodingError::WrongSize),
+        };
+
+        let mut modules = BitVec::from_iter([true, false, true, true]);
+        for (i, (&c, parity)) in data.iter().zip(parities).enumerate() {
+            if i > 0 {
+                modules.extend([false, true]);
+            }
+            modules.extend(&parity.encode(c));
+        }
+        Ok(modules)
+    }
+
+    /// Encode 2 or 5 digits as bars and spaces grouped by character, the start pattern and
+    /// each separator between digits being characters of their own
+    pub fn elements(data: &[char]) -> Result<Elements, EncodingError> {
+        let mut lengths = vec![4];
+        for i in 0..data.len() {
+            if i > 0 {
+                lengths.push(2);
+            }
+            lengths.push(7);
+        }
+        Ok(Elements::grouped(&Self::encode(data)?, &lengths))
+    }
+}
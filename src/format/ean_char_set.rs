@@ -19,7 +19,7 @@ pub fn calc_checksum<const T: usize>(chars: &[char; T]) -> u8 {
 }
 
 pub fn dyn_calc_checksum(chars: &[char]) -> u8 {
-    let parsed: Vec<u8> = chars.iter().map(|a| a.to_digit(10).unwrap() as _).collect();
+    let parsed: Vec<u32> = chars.iter().map(|a| a.to_digit(10).unwrap()).collect();
     let mut tot = 0;
     for (i, val) in parsed.iter().rev().enumerate() {
         if i % 2 != 0 {
@@ -33,7 +33,7 @@ pub fn dyn_calc_checksum(chars: &[char]) -> u8 {
     if tot == 10 {
         tot = 0;
     }
-    tot
+    tot as u8
 }
 
 pub struct ParityTable {
@@ -110,8 +110,8 @@ impl ParityTable {
                     Parity::Even,
                     Parity::Even,
                     Parity::Odd,
-                    Parity::Even,
                     Parity::Odd,
+                    Parity::Even,
                 ],
             },
             '6' => Self {
@@ -0,0 +1,69 @@
+use crate::EncodingError;
+
+/// Fraction of the width of a narrow element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fraction {
+    /// Numerator of the fraction
+    pub numerator: usize,
+    /// Denominator of the fraction, not 0
+    pub denominator: usize,
+}
+
+impl Fraction {
+    /// Fraction `numerator / denominator`
+    pub const fn new(numerator: usize, denominator: usize) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Whole number of narrow elements
+    pub const fn whole(value: usize) -> Self {
+        Self::new(value, 1)
+    }
+}
+
+/// Widths of the wide elements and of the gap between characters of width-modulated formats,
+/// as fractions of the narrow element width, the wide elements from 2.0 to 3.0 of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElementWidths {
+    /// Width of the wide elements
+    pub wide: Fraction,
+    /// Width of the space between characters
+    pub gap: Fraction,
+}
+
+impl ElementWidths {
+    /// Wide elements of the given whole width and narrow gaps
+    pub const fn whole(wide: usize) -> Self {
+        Self {
+            wide: Fraction::whole(wide),
+            gap: Fraction::whole(1),
+        }
+    }
+
+    /// Widths of the narrow elements, the wide elements and the gap, in units of the least
+    /// common multiple of the denominators.
+    ///
+    /// A zero denominator or wide elements outside of 2.0 to 3.0 narrow ones give
+    /// [`EncodingError::WrongWidths`].
+    pub(crate) fn units(&self) -> Result<(usize, usize, usize), EncodingError> {
+        let (wide, gap) = (self.wide, self.gap);
+        if wide.denominator == 0
+            || gap.denominator == 0
+            || !(2 * wide.denominator..=3 * wide.denominator).contains(&wide.numerator)
+        {
+            return Err(EncodingError::WrongWidths);
+        }
+        let gcd = |mut a: usize, mut b: usize| {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        };
+        let scale = wide.denominator / gcd(wide.denominator, gap.denominator) * gap.denominator;
+        let units = |fraction: Fraction| fraction.numerator * scale / fraction.denominator;
+        Ok((scale, units(wide), units(gap)))
+    }
+}
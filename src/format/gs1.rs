@@ -0,0 +1,209 @@
+use super::push_bits;
+use crate::EncodingError;
+
+/// FNC1 separating variable length element strings in a reduced GS1 message
+pub const GS1_FNC1: u8 = 0x1d;
+
+/// Reduce an element string written as `(01)09501101530003(10)ABC`, or with square brackets,
+/// to the AIs followed by their data, an FNC1 ending each variable length element string but
+/// the last one
+pub fn gs1_reduce(chars: &[char]) -> Result<Vec<u8>, EncodingError> {
+    let (open, close) = match chars.first() {
+        Some('(') => ('(', ')'),
+        Some('[') => ('[', ']'),
+        _ => return Err(EncodingError::WrongChar),
+    };
+
+    let mut reduced = Vec::with_capacity(chars.len());
+    let mut fixed_length = true;
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != open {
+            return Err(EncodingError::WrongChar);
+        }
+        let ai_len = chars[i + 1..].iter().position(|&c| c == close);
+        let ai = match ai_len {
+            Some(len @ 2..=4) => &chars[i + 1..i + 1 + len],
+            _ => return Err(EncodingError::WrongChar),
+        };
+        if !ai.iter().all(char::is_ascii_digit) {
+            return Err(EncodingError::WrongChar);
+        }
+        i += ai.len() + 2;
+        let data_len = chars[i..]
+            .iter()
+            .position(|&c| c == open)
+            .unwrap_or(chars.len() - i);
+        let data = &chars[i..i + data_len];
+        if data.is_empty() || !data.iter().all(|&c| c.is_ascii() && !c.is_ascii_control()) {
+            return Err(EncodingError::WrongChar);
+        }
+        i += data_len;
+
+        // GTINs are checked for a valid check digit
+        if (ai == ['0', '1'] || ai == ['0', '2']) && data.len() == 14 {
+            let digits = data.iter().map(|&c| c as u8).collect::<Vec<_>>();
+            if !digits.iter().all(u8::is_ascii_digit)
+                || gs1_check_digit(&digits[..13]) != digits[13]
+            {
+                return Err(EncodingError::WrongCheckusm);
+            }
+        }
+
+        if !fixed_length {
+            reduced.push(GS1_FNC1);
+        }
+        reduced.extend(ai.iter().chain(data).map(|&c| c as u8));
+        fixed_length = is_fixed_length(ai);
+    }
+    Ok(reduced)
+}
+
+/// Whether the AI, from its first 2 digits, is followed by data of predefined length, GS1 General
+/// Specifications Figure 7.8.5-2
+fn is_fixed_length(ai: &[char]) -> bool {
+    let prefix = (ai[0] as u8 - b'0') * 10 + ai[1] as u8 - b'0';
+    matches!(prefix, 0..=4 | 11..=20 | 23 | 31..=36 | 41)
+}
+
+/// GS1 mod 10 check digit of the ASCII `digits`
+pub fn gs1_check_digit(digits: &[u8]) -> u8 {
+    let sum = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| (d - b'0') as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum::<u32>();
+    b'0' + ((10 - sum % 10) % 10) as u8
+}
+
+/// Encodation modes of the general-purpose data field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneralFieldMode {
+    Numeric,
+    Alphanumeric,
+    IsoIec,
+}
+
+const ALPHANUMERIC_PUNCTUATION: &[u8] = b"*,-./";
+const ISO_IEC_PUNCTUATION: &[u8] = b"!\"%&'()*+,-./:;<=>?_ ";
+
+/// Smallest mode encoding the byte, FNC1 counting as numeric
+fn mode_of(byte: u8) -> Option<GeneralFieldMode> {
+    if byte == GS1_FNC1 || byte.is_ascii_digit() {
+        Some(GeneralFieldMode::Numeric)
+    } else if byte.is_ascii_uppercase() || ALPHANUMERIC_PUNCTUATION.contains(&byte) {
+        Some(GeneralFieldMode::Alphanumeric)
+    } else if byte.is_ascii_lowercase() || ISO_IEC_PUNCTUATION.contains(&byte) {
+        Some(GeneralFieldMode::IsoIec)
+    } else {
+        None
+    }
+}
+
+/// Whether the `n` bytes from `i` exist and are all of one of the `modes`
+fn next_are(field: &[u8], i: usize, n: usize, modes: &[GeneralFieldMode]) -> bool {
+    i + n <= field.len()
+        && field[i..i + n]
+            .iter()
+            .all(|&b| mode_of(b).is_some_and(|m| modes.contains(&m)))
+}
+
+/// Encode the general-purpose data field, ISO/IEC 24724 7.2.5.5 and ISO/IEC 24723 5.4.
+///
+/// Returns the last digit when an odd number of digits ends the field, its encoding depending on
+/// the space left in the symbol.
+pub fn general_field_encode(
+    field: &[u8],
+    mode: &mut GeneralFieldMode,
+    bits: &mut Vec<bool>,
+) -> Result<Option<u8>, EncodingError> {
+    use GeneralFieldMode::*;
+
+    let digit = |b: u8| if b == GS1_FNC1 { 10 } else { (b - b'0') as u32 };
+    let mut last_digit = None;
+    let mut i = 0;
+    while i < field.len() {
+        let byte = field[i];
+        let byte_mode = mode_of(byte).ok_or(EncodingError::WrongChar)?;
+        match mode {
+            Numeric => {
+                if i + 1 < field.len() {
+                    if byte_mode != Numeric || mode_of(field[i + 1]) != Some(Numeric) {
+                        push_bits(bits, 0, 4);
+                        *mode = Alphanumeric;
+                    } else {
+                        push_bits(bits, 11 * digit(byte) + digit(field[i + 1]) + 8, 7);
+                        i += 2;
+                    }
+                } else if byte_mode != Numeric {
+                    push_bits(bits, 0, 4);
+                    *mode = Alphanumeric;
+                } else {
+                    last_digit = Some(byte);
+                    i += 1;
+                }
+            }
+            Alphanumeric => {
+                if byte == GS1_FNC1 {
+                    push_bits(bits, 15, 5);
+                    *mode = Numeric;
+                    i += 1;
+                } else if byte_mode == IsoIec {
+                    push_bits(bits, 4, 5);
+                    *mode = IsoIec;
+                } else if next_are(field, i, 6, &[Numeric])
+                    || (field.len() - i <= 5
+                        && field.len() - i >= 4
+                        && next_are(field, i, field.len() - i, &[Numeric]))
+                {
+                    push_bits(bits, 0, 3);
+                    *mode = Numeric;
+                } else if byte.is_ascii_digit() {
+                    push_bits(bits, byte as u32 - 43, 5);
+                    i += 1;
+                } else if byte.is_ascii_uppercase() {
+                    push_bits(bits, byte as u32 - 33, 6);
+                    i += 1;
+                } else {
+                    let index = ALPHANUMERIC_PUNCTUATION.iter().position(|&p| p == byte);
+                    push_bits(bits, index.unwrap_or_default() as u32 + 58, 6);
+                    i += 1;
+                }
+            }
+            IsoIec => {
+                if byte == GS1_FNC1 {
+                    push_bits(bits, 15, 5);
+                    *mode = Numeric;
+                    i += 1;
+                    continue;
+                }
+                let next_10_not_iso_iec = field[i..]
+                    .iter()
+                    .take(10)
+                    .all(|&b| mode_of(b) != Some(IsoIec));
+                if next_10_not_iso_iec && next_are(field, i, 4, &[Numeric]) {
+                    push_bits(bits, 0, 3);
+                    *mode = Numeric;
+                } else if next_10_not_iso_iec && next_are(field, i, 5, &[Alphanumeric, Numeric]) {
+                    push_bits(bits, 4, 5);
+                    *mode = Alphanumeric;
+                } else if byte.is_ascii_digit() {
+                    push_bits(bits, byte as u32 - 43, 5);
+                    i += 1;
+                } else if byte.is_ascii_uppercase() {
+                    push_bits(bits, byte as u32 - 1, 7);
+                    i += 1;
+                } else if byte.is_ascii_lowercase() {
+                    push_bits(bits, byte as u32 - 7, 7);
+                    i += 1;
+                } else {
+                    let index = ISO_IEC_PUNCTUATION.iter().position(|&p| p == byte);
+                    push_bits(bits, index.unwrap_or_default() as u32 + 232, 8);
+                    i += 1;
+                }
+            }
+        }
+    }
+    Ok(last_digit)
+}
@@ -0,0 +1,99 @@
+use super::{expand_widths, CODE_128_STOP, CODE_128_WIDTHS, GS1_FNC1};
+
+/// Code set of a Code 128 symbol character, only sets B and C being needed for element strings
+/// and HIBC data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Code128Set {
+    B,
+    C,
+}
+
+const START_B: u8 = 104;
+const START_C: u8 = 105;
+const CODE_B: u8 = 100;
+const CODE_C: u8 = 99;
+const FNC1: u8 = 102;
+
+/// Symbol character values of the reduced element string, from the start character and leading
+/// FNC1 to the last data character, switching code sets for the fewest characters.
+///
+/// Returns the code set of the last data character along with the values.
+pub fn gs1_128_values(reduced: &[u8]) -> (Vec<u8>, Code128Set) {
+    code_128_values(reduced, true)
+}
+
+/// Symbol character values of printable ASCII data and [`GS1_FNC1`], from the start character
+/// and the leading FNC1 if `gs1` to the last data character, switching code sets for the
+/// fewest characters
+pub fn code_128_values(reduced: &[u8], gs1: bool) -> (Vec<u8>, Code128Set) {
+    use Code128Set::*;
+
+    let can_c = |i: usize| {
+        reduced[i] == GS1_FNC1
+            || (reduced[i].is_ascii_digit() && reduced.get(i + 1).is_some_and(u8::is_ascii_digit))
+    };
+    let step = |i: usize, set: Code128Set| match set {
+        C if reduced[i] != GS1_FNC1 => 2,
+        _ => 1,
+    };
+
+    // characters needed from `i` on and the set to use, the previous character being in the
+    // indexed set, set C winning ties
+    let mut costs = vec![[0usize; 2]; reduced.len() + 1];
+    let mut sets = vec![[B; 2]; reduced.len()];
+    for i in (0..reduced.len()).rev() {
+        for (prior, prior_set) in [B, C].into_iter().enumerate() {
+            let cost = |set: Code128Set| {
+                1 + (set != prior_set) as usize + costs[i + step(i, set)][set as usize]
+            };
+            sets[i][prior] = if can_c(i) && cost(C) <= cost(B) { C } else { B };
+            costs[i][prior] = cost(sets[i][prior]);
+        }
+    }
+
+    let mut set =
+        if !reduced.is_empty() && can_c(0) && costs[step(0, C)][C as usize] <= costs[1][B as usize]
+        {
+            C
+        } else {
+            B
+        };
+    let mut values = vec![if set == C { START_C } else { START_B }];
+    if gs1 {
+        values.push(FNC1);
+    }
+    let mut i = 0;
+    while i < reduced.len() {
+        if i > 0 {
+            let next = sets[i][set as usize];
+            if next != set {
+                values.push(if next == C { CODE_C } else { CODE_B });
+                set = next;
+            }
+        }
+        values.push(match (reduced[i], set) {
+            (GS1_FNC1, _) => FNC1,
+            (byte, B) => byte - b' ',
+            (byte, C) => (byte - b'0') * 10 + reduced[i + 1] - b'0',
+        });
+        i += step(i, set);
+    }
+    (values, set)
+}
+
+/// Modules of the symbol characters, followed by the check character and the stop character
+pub fn code_128_modules(values: &[u8]) -> Vec<bool> {
+    let check = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| i.max(1) * v as usize)
+        .sum::<usize>()
+        % 103;
+
+    let mut modules = Vec::with_capacity(11 * (values.len() + 1) + 13);
+    for &value in values.iter().chain([check as u8].iter()) {
+        expand_widths(&mut modules, &CODE_128_WIDTHS[value as usize], true);
+    }
+    expand_widths(&mut modules, &CODE_128_STOP, true);
+    modules
+}
@@ -0,0 +1,790 @@
+use super::{
+    push_bits, GaloisField, HANXIN_BLOCKS, HANXIN_DATA_CODEWORDS, HANXIN_MODULE_K, HANXIN_MODULE_M,
+    HANXIN_MODULE_R, HANXIN_TOTAL_CODEWORDS,
+};
+use crate::EncodingError;
+
+/// Han Xin Code format, for more [`info`](https://en.wikipedia.org/wiki/Han_Xin_code)
+///
+/// Chars up to `U+00FF` are encoded as ISO/IEC 8859-1, any other input is converted to GB 18030
+/// so Chinese characters use the region 1, region 2, double-byte and four-byte modes.
+pub struct HanXin;
+
+/// Han Xin Code error correction levels, recovering about 8%, 15%, 23% and 30% of the codewords
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HanXinEccLevel {
+    /// About 8% recovery
+    L1,
+    /// About 15% recovery
+    L2,
+    /// About 23% recovery
+    L3,
+    /// About 30% recovery
+    L4,
+}
+
+/// Encoded Han Xin Code symbol, a square of `size()` modules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HanXinSymbol {
+    version: u8,
+    ecc_level: HanXinEccLevel,
+    mask: u8,
+    modules: Vec<bool>,
+}
+
+/// Modules of the finder patterns, separators, function information and alignment patterns
+const FUNCTION: u8 = 0x10;
+const DARK: u8 = 0x01;
+
+/// Finder pattern rows, top left corner
+const FINDER_TOP_LEFT: [u8; 7] = [0x7f, 0x40, 0x5f, 0x50, 0x57, 0x57, 0x57];
+/// Finder pattern rows, top right and bottom left corners
+const FINDER: [u8; 7] = [0x7f, 0x01, 0x7d, 0x05, 0x75, 0x75, 0x75];
+/// Finder pattern rows, bottom right corner
+const FINDER_BOTTOM_RIGHT: [u8; 7] = [0x75, 0x75, 0x75, 0x05, 0x7d, 0x01, 0x7f];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Text,
+    Binary,
+    Region1,
+    Region2,
+    DoubleByte,
+    FourByte,
+}
+
+const MODES: [Mode; 7] = [
+    Mode::Numeric,
+    Mode::Text,
+    Mode::Binary,
+    Mode::Region1,
+    Mode::Region2,
+    Mode::DoubleByte,
+    Mode::FourByte,
+];
+
+// Mode costs are counted in sixths of a bit, so that numeric groups of 1 to 3 digits divide evenly
+
+/// Cost of the mode indicator starting the message in each mode
+const HEAD_COSTS: [u32; 7] = [24, 24, 102, 24, 24, 24, 0];
+
+/// Cost of terminating a mode and starting the next one, indexed by from and to mode
+const SWITCH_COSTS: [[u32; 7]; 7] = [
+    [0, 84, 162, 84, 84, 84, 60],
+    [60, 0, 138, 60, 60, 60, 36],
+    [24, 24, 0, 24, 24, 24, 0],
+    [96, 96, 174, 0, 72, 96, 72],
+    [96, 96, 174, 72, 0, 96, 72],
+    [114, 114, 192, 114, 114, 0, 90],
+    [24, 24, 102, 24, 24, 24, 0],
+];
+
+/// Cost of the terminator ending the message in each mode
+const END_COSTS: [u32; 7] = [60, 36, 0, 72, 72, 90, 0];
+
+impl HanXin {
+    /// Encode the provided chars in the smallest version holding them at `ecc_level`, the mask
+    /// being chosen automatically
+    pub fn encode(
+        ecc_level: HanXinEccLevel,
+        chars: &[char],
+    ) -> Result<HanXinSymbol, EncodingError> {
+        let bits = encode_bits(chars);
+        let codewords = bits.len().div_ceil(8);
+        let version = (1..=84)
+            .find(|&v| data_capacity(v, ecc_level) >= codewords)
+            .ok_or(EncodingError::TooLong)?;
+        Ok(HanXinSymbol::new(version, ecc_level, &bits))
+    }
+
+    /// Encode the provided chars in a symbol of the given `version`, from 1 to 84
+    pub fn encode_version(
+        version: u8,
+        ecc_level: HanXinEccLevel,
+        chars: &[char],
+    ) -> Result<HanXinSymbol, EncodingError> {
+        if !(1..=84).contains(&version) {
+            return Err(EncodingError::WrongSize);
+        }
+        let bits = encode_bits(chars);
+        if data_capacity(version, ecc_level) < bits.len().div_ceil(8) {
+            return Err(EncodingError::TooLong);
+        }
+        Ok(HanXinSymbol::new(version, ecc_level, &bits))
+    }
+}
+
+fn data_capacity(version: u8, ecc_level: HanXinEccLevel) -> usize {
+    HANXIN_DATA_CODEWORDS[ecc_level as usize][version as usize - 1] as usize
+}
+
+/// Values to encode, bytes when every char fits ISO/IEC 8859-1, otherwise GB 18030 single bytes
+/// and byte pairs, four-byte sequences taking two pairs
+fn code_points(chars: &[char]) -> Vec<u32> {
+    if chars.iter().all(|&c| c <= '\u{ff}') {
+        return chars.iter().map(|&c| c as u32).collect();
+    }
+    let text = chars.iter().collect::<String>();
+    let (bytes, _, _) = encoding_rs::GB18030.encode(&text);
+    let mut values = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] < 0x80 {
+            values.push(bytes[i] as u32);
+            i += 1;
+        } else {
+            values.push((bytes[i] as u32) << 8 | bytes[i + 1] as u32);
+            i += 2;
+        }
+    }
+    values
+}
+
+fn is_digit(value: u32) -> bool {
+    (b'0' as u32..=b'9' as u32).contains(&value)
+}
+
+fn is_text1(value: u32) -> bool {
+    text1_value(value).is_some()
+}
+
+/// Text submode 1 value, digits and latin letters
+fn text1_value(value: u32) -> Option<u32> {
+    match char::from_u32(value)? {
+        c @ '0'..='9' => Some(c as u32 - '0' as u32),
+        c @ 'A'..='Z' => Some(c as u32 - 'A' as u32 + 10),
+        c @ 'a'..='z' => Some(c as u32 - 'a' as u32 + 36),
+        _ => None,
+    }
+}
+
+/// Text submode 2 value, control chars and punctuation
+fn text2_value(value: u32) -> Option<u32> {
+    match value {
+        0..=27 => Some(value),
+        0x20..=0x2f => Some(value - 0x20 + 28),
+        0x3a..=0x40 => Some(value - 0x3a + 44),
+        0x5b..=0x60 => Some(value - 0x5b + 51),
+        0x7b..=0x7f => Some(value - 0x7b + 57),
+        _ => None,
+    }
+}
+
+fn is_region1(value: u32) -> bool {
+    let (first, second) = (value >> 8, value & 0xff);
+    ((0xb0..=0xd7).contains(&first) || (0xa1..=0xa3).contains(&first))
+        && (0xa1..=0xfe).contains(&second)
+        || (0xa8a1..=0xa8c0).contains(&value)
+}
+
+fn is_region2(value: u32) -> bool {
+    (0xd8..=0xf7).contains(&(value >> 8)) && (0xa1..=0xfe).contains(&(value & 0xff))
+}
+
+fn is_double_byte(value: u32) -> bool {
+    let second = value & 0xff;
+    (0x81..=0xfe).contains(&(value >> 8))
+        && ((0x40..=0x7e).contains(&second) || (0x80..=0xfe).contains(&second))
+}
+
+fn is_four_byte(value: u32, next: u32) -> bool {
+    [value, next]
+        .iter()
+        .all(|v| (0x81..=0xfe).contains(&(v >> 8)) && (0x30..=0x39).contains(&(v & 0xff)))
+}
+
+/// Mode of every value, minimizing the length of the bit stream
+fn define_modes(data: &[u32]) -> Vec<Mode> {
+    let mut numeric_end = 0;
+    let mut numeric_cost = 0;
+    let mut four_byte_end = 0;
+    let mut text_submode = 1;
+
+    let mut prev_costs = HEAD_COSTS;
+    let mut char_modes = vec![[None; 7]; data.len()];
+
+    for (i, &value) in data.iter().enumerate() {
+        let mut costs = [0; 7];
+        let modes = &mut char_modes[i];
+
+        if i >= numeric_end {
+            let digits = data[i..]
+                .iter()
+                .take(3)
+                .take_while(|&&d| is_digit(d))
+                .count();
+            numeric_end = if digits == 0 { 0 } else { i + digits };
+            numeric_cost = [0, 60, 30, 20][digits];
+        }
+        let (text1, text2) = if i < numeric_end {
+            costs[0] = prev_costs[0] + numeric_cost;
+            modes[0] = Some(Mode::Numeric);
+            (true, false)
+        } else {
+            (is_text1(value), text2_value(value).is_some())
+        };
+
+        if text1 || text2 {
+            if (text_submode == 1 && text2) || (text_submode == 2 && text1) {
+                costs[1] = prev_costs[1] + 72;
+                text_submode = if text2 { 2 } else { 1 };
+            } else {
+                costs[1] = prev_costs[1] + 36;
+            }
+            modes[1] = Some(Mode::Text);
+        } else {
+            text_submode = 1;
+        }
+
+        costs[2] = prev_costs[2] + if value > 0xff { 96 } else { 48 };
+        modes[2] = Some(Mode::Binary);
+
+        if i >= four_byte_end {
+            four_byte_end = if i + 1 < data.len() && is_four_byte(value, data[i + 1]) {
+                i + 2
+            } else {
+                0
+            };
+        }
+        if i < four_byte_end {
+            costs[6] = prev_costs[6] + 75;
+            modes[6] = Some(Mode::FourByte);
+        } else if is_double_byte(value) {
+            costs[5] = prev_costs[5] + 90;
+            modes[5] = Some(Mode::DoubleByte);
+            if is_region1(value) {
+                costs[3] = prev_costs[3] + 72;
+                modes[3] = Some(Mode::Region1);
+            } else if is_region2(value) {
+                costs[4] = prev_costs[4] + 72;
+                modes[4] = Some(Mode::Region2);
+            }
+        }
+
+        if i == data.len() - 1 {
+            for (cost, (end_cost, mode)) in costs.iter_mut().zip(END_COSTS.iter().zip(modes.iter()))
+            {
+                if mode.is_some() {
+                    *cost += end_cost;
+                }
+            }
+        }
+
+        for to in 0..7 {
+            for from in 0..7 {
+                if to != from && modes[from].is_some() {
+                    let cost = costs[from] + SWITCH_COSTS[from][to];
+                    if modes[to].is_none() || cost < costs[to] {
+                        costs[to] = cost;
+                        modes[to] = Some(MODES[from]);
+                    }
+                }
+            }
+        }
+
+        prev_costs = costs;
+    }
+
+    let mut mode = MODES[0];
+    let mut min_cost = prev_costs[0];
+    for (i, &cost) in prev_costs.iter().enumerate().skip(1) {
+        if cost < min_cost {
+            min_cost = cost;
+            mode = MODES[i];
+        }
+    }
+
+    let mut result = vec![mode; data.len()];
+    for i in (0..data.len()).rev() {
+        let index = MODES.iter().position(|&m| m == mode).unwrap();
+        mode = char_modes[i][index].unwrap();
+        result[i] = mode;
+    }
+    result
+}
+
+/// Bit stream of the data, ISO/IEC 20830 section 5.4
+fn encode_bits(chars: &[char]) -> Vec<bool> {
+    let data = code_points(chars);
+    let modes = define_modes(&data);
+    let mut bits = Vec::new();
+
+    let mut position = 0;
+    while position < data.len() {
+        let mode = modes[position];
+        let length = modes[position..].iter().take_while(|&&m| m == mode).count();
+        let block = &data[position..position + length];
+        let next = modes.get(position + length).copied();
+
+        match mode {
+            Mode::Numeric => {
+                push_bits(&mut bits, 1, 4);
+                let mut group = 0;
+                for digits in block.chunks(3) {
+                    let value = digits.iter().fold(0, |acc, &d| acc * 10 + d - b'0' as u32);
+                    push_bits(&mut bits, value, 10);
+                    group = digits.len() as u32;
+                }
+                push_bits(&mut bits, 1020 + group, 10);
+            }
+            Mode::Text => {
+                push_bits(&mut bits, 2, 4);
+                let mut submode1 = true;
+                for &value in block {
+                    if is_text1(value) != submode1 {
+                        push_bits(&mut bits, 62, 6);
+                        submode1 = !submode1;
+                    }
+                    let value = if submode1 {
+                        text1_value(value)
+                    } else {
+                        text2_value(value)
+                    };
+                    push_bits(&mut bits, value.unwrap_or_default(), 6);
+                }
+                push_bits(&mut bits, 63, 6);
+            }
+            Mode::Binary => {
+                push_bits(&mut bits, 3, 4);
+                let double_bytes = block.iter().filter(|&&v| v > 0xff).count();
+                push_bits(&mut bits, (length + double_bytes) as u32, 13);
+                for &value in block {
+                    push_bits(&mut bits, value, if value > 0xff { 16 } else { 8 });
+                }
+            }
+            Mode::Region1 | Mode::Region2 => {
+                let (other, indicator) = if mode == Mode::Region1 {
+                    (Mode::Region2, 4)
+                } else {
+                    (Mode::Region1, 5)
+                };
+                // the two regions switch into each other with the terminator alone
+                if position == 0 || modes[position - 1] != other {
+                    push_bits(&mut bits, indicator, 4);
+                }
+                for &value in block {
+                    let (first, second) = (value >> 8, value & 0xff);
+                    let glyph = if mode == Mode::Region2 {
+                        0x5e * (first - 0xd8) + second - 0xa1
+                    } else if (0xa8a1..=0xa8c0).contains(&value) {
+                        second - 0xa1 + 0xfca
+                    } else if (0xa1..=0xa3).contains(&first) {
+                        0x5e * (first - 0xa1) + second - 0xa1 + 0xeb0
+                    } else {
+                        0x5e * (first - 0xb0) + second - 0xa1
+                    };
+                    push_bits(&mut bits, glyph, 12);
+                }
+                push_bits(&mut bits, if next == Some(other) { 4094 } else { 4095 }, 12);
+            }
+            Mode::DoubleByte => {
+                push_bits(&mut bits, 6, 4);
+                for &value in block {
+                    let (first, second) = (value >> 8, value & 0xff);
+                    let offset = if second <= 0x7e { 0x40 } else { 0x41 };
+                    push_bits(&mut bits, 0xbe * (first - 0x81) + second - offset, 15);
+                }
+                push_bits(&mut bits, 32767, 15);
+            }
+            Mode::FourByte => {
+                for pair in block.chunks(2) {
+                    let (first, second) = (pair[0] >> 8, pair[0] & 0xff);
+                    let (third, fourth) = (pair[1] >> 8, pair[1] & 0xff);
+                    let glyph = 0x3138 * (first - 0x81)
+                        + 0x04ec * (second - 0x30)
+                        + 0x0a * (third - 0x81)
+                        + fourth
+                        - 0x30;
+                    push_bits(&mut bits, 7, 4);
+                    push_bits(&mut bits, glyph, 21);
+                }
+            }
+        }
+
+        position += length;
+    }
+    bits
+}
+
+/// Set `(x, y)` to `value` unless outside of the symbol or already set
+fn safe_plot(grid: &mut [u8], size: usize, x: isize, y: isize, value: u8) {
+    if (0..size as isize).contains(&x) && (0..size as isize).contains(&y) {
+        let cell = &mut grid[y as usize * size + x as usize];
+        if *cell == 0 {
+            *cell = value;
+        }
+    }
+}
+
+/// Alignment pattern along the top and right sides of a `width` by `height` region
+fn plot_alignment(grid: &mut [u8], size: usize, x: isize, y: isize, width: isize, height: isize) {
+    safe_plot(grid, size, x, y, FUNCTION | DARK);
+    safe_plot(grid, size, x - 1, y + 1, FUNCTION);
+    for i in 1..=width {
+        safe_plot(grid, size, x - i, y, FUNCTION | DARK);
+        safe_plot(grid, size, x - i - 1, y + 1, FUNCTION);
+    }
+    for i in 1..height {
+        safe_plot(grid, size, x, y + i, FUNCTION | DARK);
+        safe_plot(grid, size, x - 1, y + i + 1, FUNCTION);
+    }
+}
+
+/// Assistant alignment pattern, a dark module surrounded by light ones
+fn plot_assistant(grid: &mut [u8], size: usize, x: isize, y: isize) {
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let value = if dx == 0 && dy == 0 {
+                FUNCTION | DARK
+            } else {
+                FUNCTION
+            };
+            safe_plot(grid, size, x + dx, y + dy, value);
+        }
+    }
+}
+
+/// Grid holding the function patterns of `version`, data modules left at 0
+fn setup_grid(version: u8) -> Vec<u8> {
+    let size = 2 * version as usize + 21;
+    let mut grid = vec![0; size * size];
+
+    for (pattern, x0, y0) in [
+        (&FINDER_TOP_LEFT, 0, 0),
+        (&FINDER, 0, size - 7),
+        (&FINDER, size - 7, 0),
+        (&FINDER_BOTTOM_RIGHT, size - 7, size - 7),
+    ] {
+        for (y, row) in pattern.iter().enumerate() {
+            for x in 0..7 {
+                let dark = row & 0x40 >> x != 0;
+                grid[(y0 + y) * size + x0 + x] = if dark { FUNCTION | DARK } else { FUNCTION };
+            }
+        }
+    }
+
+    // finder separators and function information
+    for (offset, len) in [(7, 8), (8, 9)] {
+        for i in 0..len {
+            for (x, y) in [
+                (i, offset),
+                (offset, i),
+                (size - i - 1, offset),
+                (offset, size - i - 1),
+                (size - offset - 1, i),
+                (i, size - offset - 1),
+                (size - i - 1, size - offset - 1),
+                (size - offset - 1, size - i - 1),
+            ] {
+                grid[y * size + x] = FUNCTION;
+            }
+        }
+    }
+
+    if version > 3 {
+        let k = HANXIN_MODULE_K[version as usize - 1] as isize;
+        let r = HANXIN_MODULE_R[version as usize - 1] as isize;
+        let m = HANXIN_MODULE_M[version as usize - 1] as isize;
+        let last = size as isize - 1;
+        let module_len = |i: isize| if i < m { k } else { r - 1 };
+
+        // assistant alignment patterns on the left and right sides
+        let (mut y, mut i) = (0, 0);
+        while y <= last {
+            if i % 2 == 0 {
+                if m % 2 == 1 {
+                    plot_assistant(&mut grid, size, 0, y);
+                }
+            } else {
+                if m % 2 == 0 {
+                    plot_assistant(&mut grid, size, 0, y);
+                }
+                plot_assistant(&mut grid, size, last, y);
+            }
+            y += module_len(i);
+            i += 1;
+        }
+
+        // assistant alignment patterns on the top and bottom sides
+        let (mut x, mut i) = (last, 0);
+        while x >= 0 {
+            if i % 2 == 0 {
+                if m % 2 == 1 {
+                    plot_assistant(&mut grid, size, x, last);
+                }
+            } else {
+                if m % 2 == 0 {
+                    plot_assistant(&mut grid, size, x, last);
+                }
+                plot_assistant(&mut grid, size, x, 0);
+            }
+            x -= module_len(i);
+            i += 1;
+        }
+
+        let (mut y, mut row) = (0, 0);
+        while y <= last {
+            let height = module_len(row);
+            let (mut x, mut column) = (last, 0);
+            let mut plot = row % 2 == 0;
+            while x >= 0 {
+                let width = module_len(column);
+                if plot && !(y == 0 && x == last) {
+                    plot_alignment(&mut grid, size, x, y, width, height);
+                }
+                plot = !plot;
+                x -= width;
+                column += 1;
+            }
+            y += height;
+            row += 1;
+        }
+    }
+
+    grid
+}
+
+/// Data followed by the check codewords of each block, ISO/IEC 20830 Table D.1
+fn add_ecc(data: &[u8], version: u8, ecc_level: HanXinEccLevel) -> Vec<u8> {
+    let field = GaloisField::new(8, 0x163);
+    let blocks = &HANXIN_BLOCKS[version as usize - 1][ecc_level as usize];
+    let mut stream = Vec::with_capacity(HANXIN_TOTAL_CODEWORDS[version as usize - 1] as usize);
+    let mut position = 0;
+    for batch in blocks.chunks(3) {
+        let (count, data_len, ecc_len) = (batch[0] as usize, batch[1] as usize, batch[2] as usize);
+        for _ in 0..count {
+            let block = &data[position..position + data_len];
+            stream.extend_from_slice(block);
+            stream.extend(field.encode(block, ecc_len, 1));
+            position += data_len;
+        }
+    }
+    stream
+}
+
+/// Write the function information of the symbol to the reserved region around the finders
+fn set_function_info(
+    grid: &mut [u8],
+    size: usize,
+    version: u8,
+    ecc_level: HanXinEccLevel,
+    mask: u8,
+) {
+    let mut info = Vec::with_capacity(34);
+    push_bits(&mut info, version as u32 + 20, 8);
+    push_bits(&mut info, ecc_level as u32, 2);
+    push_bits(&mut info, mask as u32, 2);
+    let nibbles = info
+        .chunks(4)
+        .map(|n| n.iter().fold(0, |acc, &b| acc << 1 | b as u8))
+        .collect::<Vec<_>>();
+    for check in GaloisField::new(4, 0x13).encode(&nibbles, 4, 1) {
+        push_bits(&mut info, check as u32, 4);
+    }
+    info.resize(34, false);
+
+    for i in 0..9 {
+        let mut set = |x: usize, y: usize| grid[y * size + x] = DARK;
+        if info[i] {
+            set(i, 8);
+            set(size - i - 1, size - 9);
+        }
+        if info[i + 8] {
+            set(8, 8 - i);
+            set(size - 9, size - 9 + i);
+        }
+        if info[i + 17] {
+            set(size - 9, i);
+            set(8, size - 1 - i);
+        }
+        if info[i + 25] {
+            set(size - 9 + i, 8);
+            set(8 - i, size - 9);
+        }
+    }
+}
+
+/// Mask penalty, ISO/IEC 20830 Table 9
+fn evaluate(modules: &[u8], size: usize) -> usize {
+    let mut penalty = 0;
+
+    // 1:1:1:1:3 or 3:1:1:1:1 patterns next to 3 light modules or the symbol edge
+    for vertical in [true, false] {
+        for line in 0..size {
+            let get = |i: isize| -> Option<u8> {
+                if !(0..size as isize).contains(&i) {
+                    return None;
+                }
+                let i = i as usize;
+                Some(if vertical {
+                    modules[i * size + line]
+                } else {
+                    modules[line * size + i]
+                })
+            };
+            let at = |i: usize| get(i as isize).unwrap();
+            let mut i = 0;
+            while i + 7 <= size {
+                let found = if vertical {
+                    at(i) == 1
+                        && at(i + 1) != at(i + 5)
+                        && at(i + 2) == 1
+                        && at(i + 3) == 0
+                        && at(i + 4) == 1
+                        && at(i + 6) == 1
+                } else {
+                    let run = (0..7).map(|j| at(i + j)).collect::<Vec<_>>();
+                    run == [1, 0, 1, 0, 1, 1, 1] || run == [1, 1, 1, 0, 1, 0, 1]
+                };
+                if found {
+                    let before = (1..=3).map(|d| get(i as isize - d));
+                    let after = (7..=9).map(|d| get((i + d) as isize));
+                    if is_light_area(before) || is_light_area(after) {
+                        penalty += 50;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+        }
+    }
+
+    // runs of 3 or more modules of the same colour, the count starting light
+    for vertical in [true, false] {
+        for line in 0..size {
+            let mut run = 0;
+            let mut state = 0;
+            for i in 0..size {
+                let module = if vertical {
+                    modules[i * size + line]
+                } else {
+                    modules[line * size + i]
+                };
+                if module == state {
+                    run += 1;
+                } else {
+                    if run >= 3 {
+                        penalty += run * 4;
+                    }
+                    run = 1;
+                    state = module;
+                }
+            }
+            if run >= 3 {
+                penalty += run * 4;
+            }
+        }
+    }
+
+    penalty
+}
+
+/// Whether the 3 modules are light, the area past the symbol edge counting as light
+fn is_light_area(mut modules: impl Iterator<Item = Option<u8>>) -> bool {
+    let mut count = 0;
+    for module in modules.by_ref() {
+        match module {
+            None => return true,
+            Some(1) => return false,
+            Some(_) => count += 1,
+        }
+    }
+    count == 3
+}
+
+impl HanXinSymbol {
+    fn new(version: u8, ecc_level: HanXinEccLevel, bits: &[bool]) -> Self {
+        let size = 2 * version as usize + 21;
+        let mut data = vec![0u8; data_capacity(version, ecc_level)];
+        for (i, _) in bits.iter().enumerate().filter(|(_, &b)| b) {
+            data[i / 8] |= 0x80 >> (i % 8);
+        }
+        let stream = add_ecc(&data, version, ecc_level);
+
+        // picket fence rearrangement in 13 columns
+        let stream = (0..13)
+            .flat_map(|start| stream.iter().skip(start).step_by(13))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut grid = setup_grid(version);
+        let mut stream_bits = stream
+            .iter()
+            .flat_map(|&cw| (0..8).rev().map(move |i| cw >> i & 1 == 1));
+        for cell in grid.iter_mut().filter(|cell| **cell == 0) {
+            match stream_bits.next() {
+                Some(true) => *cell = DARK,
+                Some(false) => {}
+                None => break,
+            }
+        }
+
+        let masked = |pattern: u8, x: usize, y: usize| {
+            let (i, j) = (y + 1, x + 1);
+            match pattern {
+                1 => (i + j).is_multiple_of(2),
+                2 => ((i + j) % 3 + j % 3).is_multiple_of(2),
+                3 => (i % j + j % i + i % 3 + j % 3).is_multiple_of(2),
+                _ => false,
+            }
+        };
+        let apply = |pattern: u8| {
+            let mut modules = grid
+                .iter()
+                .enumerate()
+                .map(|(k, &cell)| {
+                    let data_module = cell & FUNCTION == 0;
+                    (cell & DARK) ^ (data_module && masked(pattern, k % size, k / size)) as u8
+                })
+                .collect::<Vec<_>>();
+            set_function_info(&mut modules, size, version, ecc_level, pattern);
+            modules
+        };
+
+        let mut best = (apply(0), 0);
+        let mut best_penalty = evaluate(&best.0, size);
+        for pattern in 1..4 {
+            let modules = apply(pattern);
+            let penalty = evaluate(&modules, size);
+            if penalty < best_penalty {
+                best_penalty = penalty;
+                best = (modules, pattern);
+            }
+        }
+
+        let (modules, mask) = best;
+        Self {
+            version,
+            ecc_level,
+            mask,
+            modules: modules.into_iter().map(|m| m == DARK).collect(),
+        }
+    }
+
+    /// Number of modules on each side
+    pub fn size(&self) -> usize {
+        2 * self.version as usize + 21
+    }
+
+    /// Symbol version, from 1 to 84
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Error correction level
+    pub fn ecc_level(&self) -> HanXinEccLevel {
+        self.ecc_level
+    }
+
+    /// Data mask pattern, from 0 to 3
+    pub fn mask(&self) -> u8 {
+        self.mask
+    }
+
+    /// Whether the module at column `x` of row `y` is dark, `false` outside of the symbol
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let size = self.size();
+        x < size && y < size && self.modules[y * size + x]
+    }
+}
@@ -0,0 +1,167 @@
+use bit_vec::BitVec;
+
+use crate::{EncodingError, Symbol};
+
+use super::{code_128_modules, code_128_values, code_39_modules, CODE_39_CHARS};
+
+/// Health Industry Bar Code format, an HIBC LIC or PAS data structure between the `+` flag
+/// character and a modulo 43 check character
+pub struct Hibc;
+
+/// Linear symbology carrying [`Hibc`] data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HibcSymbology {
+    /// Code 128, switching to its numeric code set for runs of digits
+    Code128,
+    /// Code 39 with wide elements three times as wide as narrow ones, without a check
+    /// character of its own
+    Code39,
+}
+
+/// Characters of the data structure, past the flag character
+const MAX_LEN: usize = 110;
+
+impl Hibc {
+    /// Encode the data structure following the `+` flag character as a [`Symbol::Linear`],
+    /// appending its check character.
+    ///
+    /// The data can be:
+    /// - a primary structure, the 4 character labeler identification code starting with a
+    ///   letter, a product code of 1 to 18 alphanumerics and a unit of measure digit,
+    /// - a secondary structure, followed by the link character taken from the check character
+    ///   of its primary structure, as returned by [`Hibc::check_character`],
+    /// - a primary and a secondary structure separated by `/`, the latter without link
+    ///   character,
+    /// - a PAS structure starting with `/`.
+    ///
+    /// Secondary structures hold a Julian date `YYJJJ` and a lot number of up to 13
+    /// alphanumerics, or start with `$` followed by a lot number of up to 18 alphanumerics,
+    /// `$+` followed by a serial number, or `$$` (`$$+` for a serial number) followed by an
+    /// optional quantity (`8` and 2 digits or `9` and 5 digits), the expiry date as `MMYY` or
+    /// after a format digit from `2` (`MMDDYY`) to `7` (no date), then the lot or serial
+    /// number. Lower case letters stand for upper case ones.
+    pub fn encode(symbology: HibcSymbology, data: &[char]) -> Result<Symbol, EncodingError> {
+        let chars = Self::chars(data)?;
+        Ok(Symbol::Linear(match symbology {
+            HibcSymbology::Code39 => code_39_modules(&chars)?,
+            HibcSymbology::Code128 => {
+                let bytes = chars.iter().map(|&c| c as u8).collect::<Vec<_>>();
+                let (values, _) = code_128_values(&bytes, false);
+                code_128_modules(&values).into_iter().collect::<BitVec>()
+            }
+        }))
+    }
+
+    /// Characters of the symbol, the data structure between the flag and check characters
+    pub(crate) fn chars(data: &[char]) -> Result<Vec<char>, EncodingError> {
+        let data = data
+            .iter()
+            .map(char::to_ascii_uppercase)
+            .collect::<Vec<_>>();
+        validate(&data)?;
+
+        let mut chars = vec!['+'];
+        chars.extend(&data);
+        chars.push(Self::check_character(&data)?);
+        Ok(chars)
+    }
+
+    /// Modulo 43 check character of the data structure following the `+` flag character, also
+    /// the link character of the secondary structures of a primary one
+    pub fn check_character(data: &[char]) -> Result<char, EncodingError> {
+        let sum = ['+']
+            .iter()
+            .chain(data)
+            .map(|&c| CODE_39_CHARS.find(c.to_ascii_uppercase()))
+            .sum::<Option<usize>>()
+            .ok_or(EncodingError::WrongChar)?;
+        Ok(CODE_39_CHARS.as_bytes()[sum % 43] as char)
+    }
+}
+
+fn validate(data: &[char]) -> Result<(), EncodingError> {
+    if data.len() > MAX_LEN {
+        return Err(EncodingError::TooLong);
+    }
+    if !data.iter().all(|&c| CODE_39_CHARS.contains(c)) {
+        return Err(EncodingError::WrongChar);
+    }
+
+    match data.first() {
+        Some('/') if data.len() > 1 => Ok(()),
+        Some(c) if c.is_ascii_alphabetic() => {
+            let (primary, secondary) = match data.iter().position(|&c| c == '/') {
+                Some(slash) => (&data[..slash], Some(&data[slash + 1..])),
+                None => (data, None),
+            };
+            validate_primary(primary)?;
+            secondary.map_or(Ok(()), validate_secondary)
+        }
+        Some(_) => match data.split_last() {
+            Some((_, secondary)) if !secondary.is_empty() => validate_secondary(secondary),
+            _ => Err(EncodingError::WrongSize),
+        },
+        None => Err(EncodingError::WrongSize),
+    }
+}
+
+/// Labeler identification code, product code and unit of measure
+fn validate_primary(primary: &[char]) -> Result<(), EncodingError> {
+    if !(6..=23).contains(&primary.len()) {
+        return Err(EncodingError::WrongSize);
+    }
+    let (unit, code) = primary.split_last().ok_or(EncodingError::WrongSize)?;
+    if !unit.is_ascii_digit() || !code.iter().all(char::is_ascii_alphanumeric) {
+        return Err(EncodingError::WrongChar);
+    }
+    Ok(())
+}
+
+/// Secondary structure without its link character
+fn validate_secondary(secondary: &[char]) -> Result<(), EncodingError> {
+    let digits = |chars: &[char], len: usize| -> Result<(), EncodingError> {
+        match chars.get(..len) {
+            Some(digits) if digits.iter().all(char::is_ascii_digit) => Ok(()),
+            Some(_) => Err(EncodingError::WrongChar),
+            None => Err(EncodingError::WrongSize),
+        }
+    };
+
+    let (lot, max_lot) = match secondary {
+        ['$', '$', rest @ ..] => {
+            let mut rest = rest.strip_prefix(&['+']).unwrap_or(rest);
+            let quantity = match rest.first() {
+                Some('8') => 2,
+                Some('9') => 5,
+                _ => 0,
+            };
+            if quantity > 0 {
+                digits(&rest[1..], quantity)?;
+                rest = &rest[1 + quantity..];
+            }
+            let date = match rest.first() {
+                Some('2' | '3') => 1 + 6,
+                Some('4') => 1 + 8,
+                Some('5') => 1 + 5,
+                Some('6') => 1 + 7,
+                Some('7') => 1,
+                _ => 4,
+            };
+            digits(rest, date)?;
+            (&rest[date..], 18)
+        }
+        ['$', '+', serial @ ..] => (serial, 18),
+        ['$', lot @ ..] => (lot, 18),
+        _ => {
+            digits(secondary, 5)?;
+            (&secondary[5..], 13)
+        }
+    };
+    if lot.len() > max_lot {
+        return Err(EncodingError::TooLong);
+    }
+    if !lot.iter().all(char::is_ascii_alphanumeric) {
+        return Err(EncodingError::WrongChar);
+    }
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use crate::{EncodingError, FourStateBar, Symbol};
+
+/// USPS Intelligent Mail Barcode format, USPS-B-3200
+///
+/// The 20 digit tracking code (barcode ID, service type, mailer ID and serial number) and the
+/// optional routing ZIP code are encoded in 65 4-state bars.
+pub struct IntelligentMail;
+
+/// Character and bit of the descender then of the ascender of each bar, from table IV of the
+/// specification, characters A to J being 0 to 9
+const BARS: [[(u8, u8); 2]; 65] = [
+    [(7, 2), (4, 3)],
+    [(1, 10), (0, 0)],
+    [(9, 12), (2, 8)],
+    [(5, 5), (6, 11)],
+    [(8, 9), (3, 1)],
+    [(0, 1), (5, 12)],
+    [(2, 5), (1, 8)],
+    [(4, 4), (9, 11)],
+    [(6, 3), (8, 10)],
+    [(3, 9), (7, 6)],
+    [(5, 11), (1, 4)],
+    [(8, 5), (2, 12)],
+    [(9, 10), (0, 2)],
+    [(7, 1), (6, 7)],
+    [(3, 6), (4, 9)],
+    [(0, 3), (8, 6)],
+    [(6, 4), (2, 7)],
+    [(1, 1), (9, 9)],
+    [(7, 10), (5, 2)],
+    [(4, 0), (3, 8)],
+    [(6, 2), (0, 4)],
+    [(8, 11), (1, 0)],
+    [(9, 8), (3, 12)],
+    [(2, 6), (7, 7)],
+    [(5, 1), (4, 10)],
+    [(1, 12), (6, 9)],
+    [(7, 3), (8, 0)],
+    [(5, 8), (9, 7)],
+    [(4, 6), (2, 10)],
+    [(3, 4), (0, 5)],
+    [(8, 4), (5, 7)],
+    [(7, 11), (1, 9)],
+    [(6, 0), (9, 6)],
+    [(0, 6), (4, 8)],
+    [(2, 1), (3, 2)],
+    [(5, 9), (8, 12)],
+    [(4, 11), (6, 1)],
+    [(9, 5), (7, 4)],
+    [(3, 3), (1, 2)],
+    [(0, 7), (2, 0)],
+    [(1, 3), (4, 1)],
+    [(6, 10), (3, 5)],
+    [(8, 7), (9, 4)],
+    [(2, 11), (5, 6)],
+    [(0, 8), (7, 12)],
+    [(4, 2), (8, 1)],
+    [(5, 10), (3, 0)],
+    [(9, 3), (0, 9)],
+    [(6, 5), (2, 4)],
+    [(7, 8), (1, 7)],
+    [(5, 0), (4, 5)],
+    [(2, 3), (0, 10)],
+    [(6, 12), (9, 2)],
+    [(3, 11), (1, 6)],
+    [(8, 8), (7, 9)],
+    [(5, 4), (0, 11)],
+    [(1, 5), (2, 2)],
+    [(9, 1), (4, 12)],
+    [(8, 3), (6, 6)],
+    [(7, 0), (3, 7)],
+    [(4, 7), (7, 5)],
+    [(0, 12), (1, 11)],
+    [(2, 9), (9, 0)],
+    [(6, 8), (5, 3)],
+    [(3, 10), (8, 2)],
+];
+
+impl IntelligentMail {
+    /// Encode the tracking code fields and the routing ZIP code as [`Symbol::FourState`] bars.
+    ///
+    /// The barcode ID has 2 digits, its second one from 0 to 4, the service type 3 digits, the
+    /// mailer ID 6 or 9 digits and the serial number 9 or 6, for 15 digits together. The routing
+    /// ZIP code is empty or has 5, 9 or 11 digits.
+    pub fn encode(
+        barcode_id: &[char],
+        service_type: &[char],
+        mailer_id: &[char],
+        serial: &[char],
+        routing: &[char],
+    ) -> Result<Symbol, EncodingError> {
+        if barcode_id.len() != 2
+            || service_type.len() != 3
+            || !matches!(mailer_id.len(), 6 | 9)
+            || mailer_id.len() + serial.len() != 15
+            || !matches!(routing.len(), 0 | 5 | 9 | 11)
+        {
+            return Err(EncodingError::WrongSize);
+        }
+        let tracking = [barcode_id, service_type, mailer_id, serial]
+            .concat()
+            .iter()
+            .map(|c| c.to_digit(10).map(u128::from))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(EncodingError::WrongChar)?;
+        if tracking[1] > 4 {
+            return Err(EncodingError::WrongChar);
+        }
+        let zip = routing
+            .iter()
+            .try_fold(0u128, |acc, c| Some(acc * 10 + u128::from(c.to_digit(10)?)))
+            .ok_or(EncodingError::WrongChar)?;
+
+        // binary data: the routing code offset by the smaller lengths, then the tracking code
+        let mut value = zip
+            + match routing.len() {
+                11 => 1_000_100_001,
+                9 => 100_001,
+                5 => 1,
+                _ => 0,
+            };
+        value = (value * 10 + tracking[0]) * 5 + tracking[1];
+        value = tracking[2..].iter().fold(value, |acc, d| acc * 10 + d);
+
+        let crc = crc_11(value);
+
+        // codewords A to J, A taking what remains after the lower ones
+        let mut codewords = [0u32; 10];
+        codewords[9] = (value % 636) as u32 * 2;
+        value /= 636;
+        for codeword in codewords[1..9].iter_mut().rev() {
+            *codeword = (value % 1365) as u32;
+            value /= 1365;
+        }
+        codewords[0] = value as u32 + if crc & 0x400 != 0 { 659 } else { 0 };
+
+        let five_of_13 = n_of_13(5);
+        let two_of_13 = n_of_13(2);
+        let characters = codewords
+            .iter()
+            .enumerate()
+            .map(|(i, &codeword)| {
+                let character = match codeword {
+                    0..=1286 => five_of_13[codeword as usize],
+                    _ => two_of_13[codeword as usize - 1287],
+                };
+                if crc >> i & 1 == 1 {
+                    !character & 0x1FFF
+                } else {
+                    character
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let bit = |(character, bit): (u8, u8)| characters[character as usize] >> bit & 1 == 1;
+        Ok(Symbol::FourState(
+            BARS.iter()
+                .map(
+                    |&[descender, ascender]| match (bit(ascender), bit(descender)) {
+                        (true, true) => FourStateBar::Full,
+                        (true, false) => FourStateBar::Ascender,
+                        (false, true) => FourStateBar::Descender,
+                        (false, false) => FourStateBar::Tracker,
+                    },
+                )
+                .collect(),
+        ))
+    }
+}
+
+/// Frame check sequence of the 102 bits of binary data, most significant first
+fn crc_11(value: u128) -> u16 {
+    (0..102).rev().fold(0x7FF, |fcs: u16, i| {
+        let bit = (value >> i & 1) as u16;
+        if (fcs >> 10 ^ bit) & 1 == 1 {
+            (fcs << 1 ^ 0xF35) & 0x7FF
+        } else {
+            fcs << 1 & 0x7FF
+        }
+    })
+}
+
+/// Table of the 13 bit characters with `n` bits set, palindromes last and every other character
+/// followed by its reverse
+fn n_of_13(n: u32) -> Vec<u16> {
+    let mut pairs = Vec::new();
+    let mut palindromes = Vec::new();
+    for character in 0..0x2000u16 {
+        if character.count_ones() != n {
+            continue;
+        }
+        let reverse = character.reverse_bits() >> 3;
+        if reverse == character {
+            palindromes.push(character);
+        } else if reverse > character {
+            pairs.extend([character, reverse]);
+        }
+    }
+    // the palindromes fill the table from its end
+    pairs.extend(palindromes.into_iter().rev());
+    pairs
+}
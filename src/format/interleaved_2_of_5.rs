@@ -1,37 +1,58 @@
 use bit_vec::BitVec;
 
-use crate::{format::dyn_calc_checksum, EncodingError};
+use crate::{format::dyn_calc_checksum, Elements, EncodingError};
 
-use super::{DynamicBarcodeEncoding, Width, WIDTH_MAP};
+use super::{DynamicBarcodeEncoding, ElementWidths, Width, WIDTH_MAP};
 
 /// Interleaved 2 of 5 format, for more [`info`](https://web.archive.org/web/20070202214904/http://barcodeisland.com/int2of5.phtml)
 pub struct Interleaved2Of5;
 
-impl DynamicBarcodeEncoding for Interleaved2Of5 {
-    fn encode(chars: &[char]) -> Result<bit_vec::BitVec, crate::EncodingError> {
-        if chars.len() % 2 != 0 {
+/// Widths of the start pattern
+const START: [usize; 4] = [1, 1, 1, 1];
+
+impl Interleaved2Of5 {
+    /// Wide elements of 2 modules
+    pub const WIDTHS: ElementWidths = ElementWidths::whole(2);
+
+    /// Encode the provided digits as bars and spaces grouped by pair of digits, the start and
+    /// stop patterns included, with the given wide element width, the characters having no
+    /// gap between them.
+    ///
+    /// An odd number of digits gets a check digit.
+    pub fn elements(chars: &[char], widths: ElementWidths) -> Result<Elements, EncodingError> {
+        if !chars.len().is_multiple_of(2) {
             let mut v = chars.to_vec();
             let checksum = dyn_calc_checksum(&v);
             v.push(char::from_digit(checksum.into(), 10).unwrap());
 
-            return Interleaved2Of5::encode(&v);
+            return Interleaved2Of5::elements(&v, widths);
         }
-        let mut ret = BitVec::new();
-        ret.extend([true, false, true, false]);
+        let (narrow, wide, _) = widths.units()?;
+        let mut elements = Elements {
+            scale: narrow,
+            ..Default::default()
+        };
+        elements.push_character(START.map(|width| width * narrow));
         for c in chars.chunks(2) {
             let first = c[0];
             let second = c[1];
             let first_map = WIDTH_MAP.get(&first).ok_or(EncodingError::WrongChar)?;
             let second_map = WIDTH_MAP.get(&second).ok_or(EncodingError::WrongChar)?;
-            for (is_bar, width) in EncodingIterator::new(first_map, second_map) {
-                match width {
-                    Width::Wide => ret.extend([is_bar, is_bar]),
-                    Width::Narrow => ret.push(is_bar),
-                }
-            }
+            elements.push_character(EncodingIterator::new(first_map, second_map).map(
+                |(_, width)| match width {
+                    Width::Wide => wide,
+                    Width::Narrow => narrow,
+                },
+            ));
         }
-        ret.extend([true, true, false, true]);
-        Ok(ret)
+        elements.push_character([wide, narrow, narrow]);
+        Ok(elements)
+    }
+}
+
+impl DynamicBarcodeEncoding for Interleaved2Of5 {
+    fn encode(chars: &[char]) -> Result<BitVec, EncodingError> {
+        Ok(Interleaved2Of5::elements(chars, Interleaved2Of5::WIDTHS)?.modules())
     }
 }
 
@@ -58,7 +79,7 @@ impl<'a> Iterator for EncodingIterator<'a> {
         if self.current == 10 {
             return None;
         }
-        if self.current % 2 == 0 {
+        if self.current.is_multiple_of(2) {
             let idx = self.current / 2;
             self.current += 1;
             Some((true, self.first_chars[idx]))
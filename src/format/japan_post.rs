@@ -0,0 +1,188 @@
+use crate::{EncodingError, FourStateBar, Symbol};
+
+/// Japan Post 4-state customer barcode format, the postal code followed by the address number
+pub struct JapanPost;
+
+const F: FourStateBar = FourStateBar::Full;
+const A: FourStateBar = FourStateBar::Ascender;
+const D: FourStateBar = FourStateBar::Descender;
+const T: FourStateBar = FourStateBar::Tracker;
+
+/// Bars of the symbol characters by value, the digits then `-` then the control codes CC1 to
+/// CC8
+const CHARACTERS: [[FourStateBar; 3]; 19] = [
+    [F, T, T],
+    [F, F, T],
+    [F, D, A],
+    [D, F, A],
+    [F, A, D],
+    [F, T, F],
+    [D, A, F],
+    [A, F, D],
+    [A, D, F],
+    [T, F, F],
+    [T, F, T],
+    [D, A, T],
+    [D, T, A],
+    [A, D, T],
+    [T, D, A],
+    [A, T, D],
+    [T, A, D],
+    [T, T, F],
+    [F, F, F],
+];
+
+const HYPHEN: u8 = 10;
+const CC1: u8 = 11;
+const CC4: u8 = 14;
+
+/// Symbol characters between the start and check characters
+const DATA_LEN: usize = 20;
+
+impl JapanPost {
+    /// Encode the digits, letters and `-` of the postal code and address number as
+    /// [`Symbol::FourState`] bars.
+    ///
+    /// Letters take a control code and a digit, lower case ones standing for upper case ones.
+    /// The data is padded with CC4 up to 20 symbol characters, anything past them being dropped
+    /// as the customer barcode specification requires.
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        let mut values = Vec::with_capacity(DATA_LEN + 1);
+        for c in data.iter().map(char::to_ascii_uppercase) {
+            match c {
+                '0'..='9' => values.push(c as u8 - b'0'),
+                '-' => values.push(HYPHEN),
+                'A'..='Z' => {
+                    let index = c as u8 - b'A';
+                    values.extend([CC1 + index / 10, index % 10]);
+                }
+                _ => return Err(EncodingError::WrongChar),
+            }
+        }
+        values.resize(DATA_LEN, CC4);
+
+        let sum = values.iter().map(|&v| v as usize).sum::<usize>();
+        values.push(((19 - sum % 19) % 19) as u8);
+
+        let mut bars = vec![F, D];
+        bars.extend(values.iter().flat_map(|&v| CHARACTERS[v as usize]));
+        bars.extend([D, F]);
+        Ok(Symbol::FourState(bars))
+    }
+
+    /// Extract the data of a customer barcode from the postal code and the address following
+    /// the town name, as `1-2-3` for `1丁目2番3号`.
+    ///
+    /// The numbers of the address, Arabic or kanji, and its single letters are kept, anything
+    /// else separating them with a single `-`. Full width digits, letters and hyphens stand for
+    /// their ASCII counterparts.
+    pub fn address_number(
+        postal_code: &[char],
+        address: &[char],
+    ) -> Result<Vec<char>, EncodingError> {
+        let mut data = postal_code
+            .iter()
+            .copied()
+            .map(half_width)
+            .filter(|&c| c != '-')
+            .collect::<Vec<_>>();
+        if data.len() != 7 {
+            return Err(EncodingError::WrongSize);
+        }
+        if !data.iter().all(char::is_ascii_digit) {
+            return Err(EncodingError::WrongChar);
+        }
+
+        let address = address.iter().copied().map(half_width).collect::<Vec<_>>();
+        let letter = |i: usize| address.get(i).is_some_and(char::is_ascii_alphabetic);
+        let mut separated = false;
+        let mut i = 0;
+        while i < address.len() {
+            let c = address[i];
+            let token = if c.is_ascii_digit() {
+                let end = (i..address.len())
+                    .find(|&j| !address[j].is_ascii_digit())
+                    .unwrap_or(address.len());
+                let digits = address[i..end].to_vec();
+                i = end;
+                Some(digits)
+            } else if kanji_digit(c).is_some() || kanji_unit(c).is_some() {
+                let end = (i..address.len())
+                    .find(|&j| {
+                        kanji_digit(address[j]).is_none() && kanji_unit(address[j]).is_none()
+                    })
+                    .unwrap_or(address.len());
+                let digits = kanji_number(&address[i..end]).to_string().chars().collect();
+                i = end;
+                Some(digits)
+            } else if c.is_ascii_alphabetic() {
+                // words of several letters, as building names, are not part of the address number
+                let single = !letter(i + 1) && (i == 0 || !letter(i - 1));
+                i += 1;
+                single.then(|| vec![c.to_ascii_uppercase()])
+            } else {
+                i += 1;
+                None
+            };
+
+            match token {
+                Some(token) => {
+                    if separated && data.len() > 7 {
+                        data.push('-');
+                    }
+                    separated = false;
+                    data.extend(token);
+                }
+                None => separated = true,
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// ASCII counterpart of a full width digit, letter or hyphen
+fn half_width(c: char) -> char {
+    match c {
+        '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' => {
+            char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c)
+        }
+        '－' | '‐' | '−' | '‑' | '–' | '—' => '-',
+        _ => c,
+    }
+}
+
+fn kanji_digit(c: char) -> Option<u32> {
+    "〇一二三四五六七八九"
+        .chars()
+        .position(|k| k == c)
+        .map(|d| d as u32)
+}
+
+fn kanji_unit(c: char) -> Option<u32> {
+    match c {
+        '十' => Some(10),
+        '百' => Some(100),
+        '千' => Some(1000),
+        _ => None,
+    }
+}
+
+/// Value of kanji numerals, written positionally as `二〇三` or with units as `二百三`
+fn kanji_number(kanji: &[char]) -> u32 {
+    if kanji.iter().all(|&c| kanji_digit(c).is_some()) {
+        return kanji
+            .iter()
+            .fold(0, |acc, &c| acc * 10 + kanji_digit(c).unwrap_or(0));
+    }
+    let (mut total, mut digit) = (0, None);
+    for &c in kanji {
+        match kanji_unit(c) {
+            Some(unit) => {
+                total += digit.unwrap_or(1) * unit;
+                digit = None;
+            }
+            None => digit = kanji_digit(c),
+        }
+    }
+    total + digit.unwrap_or(0)
+}
@@ -0,0 +1,473 @@
+use crate::EncodingError;
+
+use super::{
+    GaloisField, MaxiCodeSet, MAXICODE_2_SHIFT_A, MAXICODE_3_SHIFT_A, MAXICODE_LATCH,
+    MAXICODE_LATCH_A_FROM_CDE, MAXICODE_NS, MAXICODE_PAD, MAXICODE_PAD_E, MAXICODE_SET_A,
+};
+
+/// MaxiCode format, for more [`info`](https://en.wikipedia.org/wiki/MaxiCode)
+pub struct MaxiCode;
+
+/// Structured Carrier Message held by the primary message of modes 2 and 3
+#[derive(Debug, Clone)]
+pub struct CarrierMessage {
+    /// Postal code, up to 9 digits (mode 2) or up to 6 Code Set A characters (mode 3)
+    pub postal_code: String,
+    /// ISO 3166 numeric country code
+    pub country: u16,
+    /// Carrier service class
+    pub service_class: u16,
+}
+
+/// MaxiCode modes
+#[derive(Debug, Clone)]
+pub enum MaxiCodeMode {
+    /// Structured Carrier Message, mode 2 for numeric postal codes and mode 3 otherwise
+    Structured(CarrierMessage),
+    /// Mode 4, standard error correction
+    Standard,
+    /// Mode 5, enhanced error correction
+    EnhancedEcc,
+    /// Mode 6, reader programming
+    ReaderProgramming,
+}
+
+/// Encoded MaxiCode symbol, 33 rows of 30 hexagonal modules with odd rows offset half a module to the right
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxiCodeSymbol {
+    modules: [[bool; 30]; 33],
+}
+
+/// Central finder pattern of a [`MaxiCodeSymbol`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bullseye {
+    /// Centre of the rings, in module widths from the top left corner
+    pub center: (f64, f64),
+    /// Radii of the six circles, outermost first, in module widths.
+    ///
+    /// Even indices start a dark ring, odd indices a light one.
+    pub radii: [f64; 6],
+}
+
+const LIGHT: i16 = -1;
+const DARK: i16 = -2;
+
+/// Bit of the codeword stream held by each module, ISO/IEC 16023 Figure 5.
+///
+/// [`LIGHT`] and [`DARK`] mark the orientation patterns, -3 the modules covered by the bullseye
+/// and the unused end of the odd rows.
+#[rustfmt::skip]
+const GRID: [[i16; 30]; 33] = [
+    [121, 120, 127, 126, 133, 132, 139, 138, 145, 144, 151, 150, 157, 156, 163, 162, 169, 168, 175, 174, 181, 180, 187, 186, 193, 192, 199, 198, -2, -2],
+    [123, 122, 129, 128, 135, 134, 141, 140, 147, 146, 153, 152, 159, 158, 165, 164, 171, 170, 177, 176, 183, 182, 189, 188, 195, 194, 201, 200, 816, -3],
+    [125, 124, 131, 130, 137, 136, 143, 142, 149, 148, 155, 154, 161, 160, 167, 166, 173, 172, 179, 178, 185, 184, 191, 190, 197, 196, 203, 202, 818, 817],
+    [283, 282, 277, 276, 271, 270, 265, 264, 259, 258, 253, 252, 247, 246, 241, 240, 235, 234, 229, 228, 223, 222, 217, 216, 211, 210, 205, 204, 819, -3],
+    [285, 284, 279, 278, 273, 272, 267, 266, 261, 260, 255, 254, 249, 248, 243, 242, 237, 236, 231, 230, 225, 224, 219, 218, 213, 212, 207, 206, 821, 820],
+    [287, 286, 281, 280, 275, 274, 269, 268, 263, 262, 257, 256, 251, 250, 245, 244, 239, 238, 233, 232, 227, 226, 221, 220, 215, 214, 209, 208, 822, -3],
+    [289, 288, 295, 294, 301, 300, 307, 306, 313, 312, 319, 318, 325, 324, 331, 330, 337, 336, 343, 342, 349, 348, 355, 354, 361, 360, 367, 366, 824, 823],
+    [291, 290, 297, 296, 303, 302, 309, 308, 315, 314, 321, 320, 327, 326, 333, 332, 339, 338, 345, 344, 351, 350, 357, 356, 363, 362, 369, 368, 825, -3],
+    [293, 292, 299, 298, 305, 304, 311, 310, 317, 316, 323, 322, 329, 328, 335, 334, 341, 340, 347, 346, 353, 352, 359, 358, 365, 364, 371, 370, 827, 826],
+    [409, 408, 403, 402, 397, 396, 391, 390, 79, 78, -2, -2, 13, 12, 37, 36, 2, -1, 44, 43, 109, 108, 385, 384, 379, 378, 373, 372, 828, -3],
+    [411, 410, 405, 404, 399, 398, 393, 392, 81, 80, 40, -2, 15, 14, 39, 38, 3, -1, -1, 45, 111, 110, 387, 386, 381, 380, 375, 374, 830, 829],
+    [413, 412, 407, 406, 401, 400, 395, 394, 83, 82, 41, -3, -3, -3, -3, -3, 5, 4, 47, 46, 113, 112, 389, 388, 383, 382, 377, 376, 831, -3],
+    [415, 414, 421, 420, 427, 426, 103, 102, 55, 54, 16, -3, -3, -3, -3, -3, -3, -3, 20, 19, 85, 84, 433, 432, 439, 438, 445, 444, 833, 832],
+    [417, 416, 423, 422, 429, 428, 105, 104, 57, 56, -3, -3, -3, -3, -3, -3, -3, -3, 22, 21, 87, 86, 435, 434, 441, 440, 447, 446, 834, -3],
+    [419, 418, 425, 424, 431, 430, 107, 106, 59, 58, -3, -3, -3, -3, -3, -3, -3, -3, -3, 23, 89, 88, 437, 436, 443, 442, 449, 448, 836, 835],
+    [481, 480, 475, 474, 469, 468, 48, -2, 30, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, 0, 53, 52, 463, 462, 457, 456, 451, 450, 837, -3],
+    [483, 482, 477, 476, 471, 470, 49, -1, -2, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -2, -1, 465, 464, 459, 458, 453, 452, 839, 838],
+    [485, 484, 479, 478, 473, 472, 51, 50, 31, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, 1, -2, 42, 467, 466, 461, 460, 455, 454, 840, -3],
+    [487, 486, 493, 492, 499, 498, 97, 96, 61, 60, -3, -3, -3, -3, -3, -3, -3, -3, -3, 26, 91, 90, 505, 504, 511, 510, 517, 516, 842, 841],
+    [489, 488, 495, 494, 501, 500, 99, 98, 63, 62, -3, -3, -3, -3, -3, -3, -3, -3, 28, 27, 93, 92, 507, 506, 513, 512, 519, 518, 843, -3],
+    [491, 490, 497, 496, 503, 502, 101, 100, 65, 64, 17, -3, -3, -3, -3, -3, -3, -3, 18, 29, 95, 94, 509, 508, 515, 514, 521, 520, 845, 844],
+    [559, 558, 553, 552, 547, 546, 541, 540, 73, 72, 32, -3, -3, -3, -3, -3, -3, 10, 67, 66, 115, 114, 535, 534, 529, 528, 523, 522, 846, -3],
+    [561, 560, 555, 554, 549, 548, 543, 542, 75, 74, -2, -1, 7, 6, 35, 34, 11, -2, 69, 68, 117, 116, 537, 536, 531, 530, 525, 524, 848, 847],
+    [563, 562, 557, 556, 551, 550, 545, 544, 77, 76, -2, 33, 9, 8, 25, 24, -1, -2, 71, 70, 119, 118, 539, 538, 533, 532, 527, 526, 849, -3],
+    [565, 564, 571, 570, 577, 576, 583, 582, 589, 588, 595, 594, 601, 600, 607, 606, 613, 612, 619, 618, 625, 624, 631, 630, 637, 636, 643, 642, 851, 850],
+    [567, 566, 573, 572, 579, 578, 585, 584, 591, 590, 597, 596, 603, 602, 609, 608, 615, 614, 621, 620, 627, 626, 633, 632, 639, 638, 645, 644, 852, -3],
+    [569, 568, 575, 574, 581, 580, 587, 586, 593, 592, 599, 598, 605, 604, 611, 610, 617, 616, 623, 622, 629, 628, 635, 634, 641, 640, 647, 646, 854, 853],
+    [727, 726, 721, 720, 715, 714, 709, 708, 703, 702, 697, 696, 691, 690, 685, 684, 679, 678, 673, 672, 667, 666, 661, 660, 655, 654, 649, 648, 855, -3],
+    [729, 728, 723, 722, 717, 716, 711, 710, 705, 704, 699, 698, 693, 692, 687, 686, 681, 680, 675, 674, 669, 668, 663, 662, 657, 656, 651, 650, 857, 856],
+    [731, 730, 725, 724, 719, 718, 713, 712, 707, 706, 701, 700, 695, 694, 689, 688, 683, 682, 677, 676, 671, 670, 665, 664, 659, 658, 653, 652, 858, -3],
+    [733, 732, 739, 738, 745, 744, 751, 750, 757, 756, 763, 762, 769, 768, 775, 774, 781, 780, 787, 786, 793, 792, 799, 798, 805, 804, 811, 810, 860, 859],
+    [735, 734, 741, 740, 747, 746, 753, 752, 759, 758, 765, 764, 771, 770, 777, 776, 783, 782, 789, 788, 795, 794, 801, 800, 807, 806, 813, 812, 861, -3],
+    [737, 736, 743, 742, 749, 748, 755, 754, 761, 760, 767, 766, 773, 772, 779, 778, 785, 784, 791, 790, 797, 796, 803, 802, 809, 808, 815, 814, 863, 862],
+];
+
+impl MaxiCode {
+    /// Encode `chars` as the secondary message of modes 2 and 3, or the whole message otherwise
+    pub fn encode(mode: &MaxiCodeMode, chars: &[char]) -> Result<MaxiCodeSymbol, EncodingError> {
+        let secondary_len = match mode {
+            MaxiCodeMode::EnhancedEcc => 68,
+            _ => 84,
+        };
+
+        let mut codewords = [0u8; 144];
+        match mode {
+            MaxiCodeMode::Structured(carrier) => {
+                codewords[..10].copy_from_slice(&carrier.primary_codewords()?);
+                let data = encode_text(chars, secondary_len)?;
+                codewords[20..20 + secondary_len].copy_from_slice(&data);
+            }
+            _ => {
+                codewords[0] = match mode {
+                    MaxiCodeMode::EnhancedEcc => 5,
+                    MaxiCodeMode::ReaderProgramming => 6,
+                    _ => 4,
+                };
+                let data = encode_text(chars, 9 + secondary_len)?;
+                codewords[1..10].copy_from_slice(&data[..9]);
+                codewords[20..20 + secondary_len].copy_from_slice(&data[9..]);
+            }
+        }
+
+        let field = GaloisField::new(6, 0x43);
+        let primary_ecc = field.encode(&codewords[..10], 10, 1);
+        codewords[10..20].copy_from_slice(&primary_ecc);
+
+        // the secondary message is split in two interleaved halves, each with its own check codewords
+        let ecc_len = (144 - 20 - secondary_len) / 2;
+        for parity in 0..2 {
+            let data = codewords[20 + parity..20 + secondary_len]
+                .iter()
+                .step_by(2)
+                .copied()
+                .collect::<Vec<_>>();
+            for (j, ecc) in field.encode(&data, ecc_len, 1).into_iter().enumerate() {
+                codewords[20 + secondary_len + 2 * j + parity] = ecc;
+            }
+        }
+
+        let mut modules = [[false; 30]; 33];
+        for (row, bits) in modules.iter_mut().zip(GRID.iter()) {
+            for (module, &bit) in row.iter_mut().zip(bits.iter()) {
+                *module = match bit {
+                    DARK => true,
+                    LIGHT => false,
+                    b if b >= 0 => {
+                        let b = b as usize;
+                        codewords[b / 6] & (1 << (5 - b % 6)) != 0
+                    }
+                    _ => false,
+                };
+            }
+        }
+        Ok(MaxiCodeSymbol { modules })
+    }
+}
+
+impl CarrierMessage {
+    fn primary_codewords(&self) -> Result<[u8; 10], EncodingError> {
+        if self.country > 999 || self.service_class > 999 {
+            return Err(EncodingError::WrongSize);
+        }
+        let country = self.country as u32;
+        let service = self.service_class as u32;
+        let mut postal_code = self.postal_code.chars().collect::<Vec<_>>();
+        // an unknown US "+4" is filled with zeroes
+        if country == 840 && postal_code.len() == 5 && postal_code.iter().all(char::is_ascii_digit)
+        {
+            postal_code.extend(['0'; 4]);
+        }
+
+        let mut ret = [0u32; 10];
+        if !postal_code.is_empty()
+            && postal_code.len() <= 9
+            && postal_code.iter().all(|c| c.is_ascii_digit())
+        {
+            let len = postal_code.len() as u32;
+            let code = postal_code
+                .iter()
+                .fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap());
+            ret[0] = ((code & 0x03) << 4) | 2;
+            ret[1] = (code & 0xfc) >> 2;
+            ret[2] = (code & 0x3f00) >> 8;
+            ret[3] = (code & 0xfc000) >> 14;
+            ret[4] = (code & 0x3f00000) >> 20;
+            ret[5] = ((code & 0x3c000000) >> 26) | ((len & 0x03) << 4);
+            ret[6] = ((len & 0x3c) >> 2) | ((country & 0x03) << 4);
+        } else {
+            if postal_code.len() > 6 {
+                return Err(EncodingError::WrongSize);
+            }
+            let mut code = [32u32; 6];
+            for (value, c) in code.iter_mut().zip(postal_code.iter()) {
+                if c.is_control() {
+                    return Err(EncodingError::WrongChar);
+                }
+                *value = MAXICODE_SET_A
+                    .get(&c.to_ascii_uppercase())
+                    .copied()
+                    .ok_or(EncodingError::WrongChar)? as u32;
+            }
+            ret[0] = ((code[5] & 0x03) << 4) | 3;
+            for i in 1..6 {
+                ret[i] = ((code[5 - i] & 0x03) << 4) | ((code[6 - i] & 0x3c) >> 2);
+            }
+            ret[6] = ((code[0] & 0x3c) >> 2) | ((country & 0x03) << 4);
+        }
+        ret[7] = (country & 0xfc) >> 2;
+        ret[8] = ((country & 0x300) >> 8) | ((service & 0x0f) << 2);
+        ret[9] = (service & 0x3f0) >> 4;
+        Ok(ret.map(|v| v as u8))
+    }
+}
+
+/// Code sets in the order ties are broken, those with a pad character first
+const CODE_SETS: [MaxiCodeSet; 5] = [
+    MaxiCodeSet::A,
+    MaxiCodeSet::B,
+    MaxiCodeSet::E,
+    MaxiCodeSet::C,
+    MaxiCodeSet::D,
+];
+
+/// Codewords switching from code set `from` to `to` for the following characters
+fn latch(from: MaxiCodeSet, to: MaxiCodeSet) -> Vec<u8> {
+    match (from, to) {
+        _ if from == to => vec![],
+        (MaxiCodeSet::B, MaxiCodeSet::A) | (_, MaxiCodeSet::B) => vec![MAXICODE_LATCH],
+        (_, MaxiCodeSet::A) => vec![MAXICODE_LATCH_A_FROM_CDE],
+        // shifting twice into code sets C, D and E locks them in
+        _ => vec![to.shift(); 2],
+    }
+}
+
+/// Ways of encoding the characters ending at a given position
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    /// 9 digits after a numeric shift
+    Digits,
+    /// A character of the current code set
+    Char,
+    /// A character shifted from another code set
+    Shift(MaxiCodeSet),
+    /// 2 or 3 Code Set A characters after a multiple shift
+    ShiftA(usize),
+}
+
+impl Op {
+    /// Operations available from each code set, in the order ties are broken
+    fn from_set(set: MaxiCodeSet) -> &'static [Op] {
+        match set {
+            MaxiCodeSet::A => &[
+                Op::Char,
+                Op::Shift(MaxiCodeSet::B),
+                Op::Shift(MaxiCodeSet::E),
+                Op::Shift(MaxiCodeSet::C),
+                Op::Shift(MaxiCodeSet::D),
+            ],
+            MaxiCodeSet::B => &[
+                Op::Char,
+                Op::Shift(MaxiCodeSet::A),
+                Op::ShiftA(2),
+                Op::ShiftA(3),
+                Op::Shift(MaxiCodeSet::E),
+                Op::Shift(MaxiCodeSet::C),
+                Op::Shift(MaxiCodeSet::D),
+            ],
+            MaxiCodeSet::E => &[
+                Op::Char,
+                Op::Shift(MaxiCodeSet::C),
+                Op::Shift(MaxiCodeSet::D),
+            ],
+            MaxiCodeSet::C => &[
+                Op::Char,
+                Op::Shift(MaxiCodeSet::E),
+                Op::Shift(MaxiCodeSet::D),
+            ],
+            MaxiCodeSet::D => &[
+                Op::Char,
+                Op::Shift(MaxiCodeSet::E),
+                Op::Shift(MaxiCodeSet::C),
+            ],
+        }
+    }
+
+    /// Number of characters consumed
+    fn intake(self) -> usize {
+        match self {
+            Op::Digits => 9,
+            Op::Char | Op::Shift(_) => 1,
+            Op::ShiftA(n) => n,
+        }
+    }
+
+    /// Number of codewords produced
+    fn len(self) -> usize {
+        match self {
+            Op::Digits => 6,
+            Op::Char => 1,
+            Op::Shift(_) => 2,
+            Op::ShiftA(n) => n + 1,
+        }
+    }
+
+    fn encode(self, set: MaxiCodeSet, chars: &[char]) -> Vec<u8> {
+        match self {
+            Op::Digits => {
+                let value = chars
+                    .iter()
+                    .fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap());
+                let mut ret = vec![MAXICODE_NS];
+                ret.extend((0..5).rev().map(|k| ((value >> (6 * k)) & 0x3f) as u8));
+                ret
+            }
+            Op::Char => vec![set.value_of(chars[0]).unwrap()],
+            Op::Shift(target) => vec![target.shift(), target.value_of(chars[0]).unwrap()],
+            Op::ShiftA(n) => {
+                let mut ret = vec![if n == 2 {
+                    MAXICODE_2_SHIFT_A
+                } else {
+                    MAXICODE_3_SHIFT_A
+                }];
+                ret.extend(chars.iter().map(|&c| MaxiCodeSet::A.value_of(c).unwrap()));
+                ret
+            }
+        }
+    }
+}
+
+/// Encode `chars` into exactly `capacity` codewords with the fewest codewords possible, padding
+/// the unused space
+fn encode_text(chars: &[char], capacity: usize) -> Result<Vec<u8>, EncodingError> {
+    if chars
+        .iter()
+        .any(|&c| CODE_SETS.iter().all(|s| s.value_of(c).is_none()))
+    {
+        return Err(EncodingError::WrongChar);
+    }
+
+    // lengths[k][s]: shortest encoding of the first k chars ending in code set s, origins[k][s]:
+    // the code set to switch from into s after the first k chars
+    let mut lengths = vec![[0usize; 5]; chars.len() + 1];
+    let mut origins = vec![[0usize; 5]; chars.len() + 1];
+    // operation encoding each char, and the code set it starts from
+    let mut paths = vec![[(Op::Char, 0); 5]; chars.len()];
+
+    let mut digits = 0;
+    let mut num_a = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        digits = if c.is_ascii_digit() { digits + 1 } else { 0 };
+        num_a = if MaxiCodeSet::A.value_of(c).is_some() {
+            num_a + 1
+        } else {
+            0
+        };
+
+        for (s, &set) in CODE_SETS.iter().enumerate() {
+            // nothing can beat a numeric shift
+            let ops = if digits >= 9 {
+                &[Op::Digits]
+            } else {
+                Op::from_set(set)
+            };
+            let mut best = usize::MAX;
+            for &op in ops {
+                let possible = match op {
+                    Op::Digits => true,
+                    Op::Char => set.value_of(c).is_some(),
+                    Op::Shift(target) => target.value_of(c).is_some(),
+                    Op::ShiftA(n) => num_a >= n,
+                };
+                if !possible {
+                    continue;
+                }
+                let row = i + 1 - op.intake();
+                let origin = origins[row][s];
+                let len = lengths[row][origin]
+                    .saturating_add(latch(CODE_SETS[origin], set).len() + op.len());
+                if len < best {
+                    best = len;
+                    paths[i][s] = (op, origin);
+                }
+            }
+            lengths[i + 1][s] = best;
+        }
+        for (s, &set) in CODE_SETS.iter().enumerate() {
+            origins[i + 1][s] = (0..5)
+                .min_by_key(|&o| lengths[i + 1][o].saturating_add(latch(CODE_SETS[o], set).len()))
+                .unwrap();
+        }
+    }
+
+    let last = (0..5).min_by_key(|&s| lengths[chars.len()][s]).unwrap();
+    let mut chunks = Vec::new();
+    let mut state = last;
+    let mut i = chars.len();
+    while i > 0 {
+        let (op, origin) = paths[i - 1][state];
+        i -= op.intake();
+        chunks.push(op.encode(CODE_SETS[state], &chars[i..i + op.intake()]));
+        chunks.push(latch(CODE_SETS[origin], CODE_SETS[state]));
+        state = origin;
+    }
+    let mut ret = chunks.into_iter().rev().flatten().collect::<Vec<_>>();
+
+    if ret.len() > capacity {
+        return Err(EncodingError::TooLong);
+    }
+    let pad = match CODE_SETS[last] {
+        MaxiCodeSet::E => MAXICODE_PAD_E,
+        MaxiCodeSet::C | MaxiCodeSet::D => {
+            if ret.len() < capacity {
+                ret.push(MAXICODE_LATCH_A_FROM_CDE);
+            }
+            MAXICODE_PAD
+        }
+        _ => MAXICODE_PAD,
+    };
+    ret.resize(capacity, pad);
+    Ok(ret)
+}
+
+impl MaxiCodeSymbol {
+    /// Number of module rows
+    pub const ROWS: usize = 33;
+    /// Number of modules in each row
+    pub const COLUMNS: usize = 30;
+
+    /// Whether the module at column `x` of row `y` is dark, `false` outside of the symbol
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.modules
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Width and height of the symbol, in module widths
+    pub fn size(&self) -> (f64, f64) {
+        (
+            Self::COLUMNS as f64 + 0.5,
+            (Self::ROWS - 1) as f64 * 3f64.sqrt() / 2.0 + 2.0 / 3f64.sqrt(),
+        )
+    }
+
+    /// Centre of the hexagon at column `x` of row `y`, in module widths from the top left corner.
+    ///
+    /// Hexagons are one module wide between their vertical sides and have a vertex pointing up.
+    pub fn module_center(x: usize, y: usize) -> (f64, f64) {
+        let offset = if y % 2 == 1 { 0.5 } else { 0.0 };
+        (
+            x as f64 + 0.5 + offset,
+            y as f64 * 3f64.sqrt() / 2.0 + 1.0 / 3f64.sqrt(),
+        )
+    }
+
+    /// Centres of the dark hexagons, ready to be drawn by a renderer
+    pub fn coordinates(&self) -> Vec<(f64, f64)> {
+        (0..Self::ROWS)
+            .flat_map(|y| (0..Self::COLUMNS).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.get(x, y))
+            .map(|(x, y)| Self::module_center(x, y))
+            .collect()
+    }
+
+    /// Geometry of the central finder pattern
+    pub fn bullseye(&self) -> Bullseye {
+        Bullseye {
+            center: Self::module_center(14, 16),
+            radii: [4.571, 3.779, 2.988, 2.196, 1.394, 0.602],
+        }
+    }
+}
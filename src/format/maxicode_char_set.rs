@@ -0,0 +1,98 @@
+use phf::phf_map;
+
+/// MaxiCode code sets, ISO/IEC 16023 Table 2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxiCodeSet {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+impl MaxiCodeSet {
+    pub fn value_of(&self, c: char) -> Option<u8> {
+        let map = match self {
+            MaxiCodeSet::A => &MAXICODE_SET_A,
+            MaxiCodeSet::B => &MAXICODE_SET_B,
+            MaxiCodeSet::C => &MAXICODE_SET_C,
+            MaxiCodeSet::D => &MAXICODE_SET_D,
+            MaxiCodeSet::E => &MAXICODE_SET_E,
+        };
+        map.get(&c).copied()
+    }
+
+    /// Value of the single character shift into `self`, valid from every other code set
+    pub fn shift(&self) -> u8 {
+        match self {
+            MaxiCodeSet::A | MaxiCodeSet::B => 59,
+            MaxiCodeSet::C => 60,
+            MaxiCodeSet::D => 61,
+            MaxiCodeSet::E => 62,
+        }
+    }
+}
+
+pub const MAXICODE_NS: u8 = 31;
+pub const MAXICODE_PAD: u8 = 33;
+pub const MAXICODE_PAD_E: u8 = 28;
+pub const MAXICODE_2_SHIFT_A: u8 = 56;
+pub const MAXICODE_3_SHIFT_A: u8 = 57;
+pub const MAXICODE_LATCH_A_FROM_CDE: u8 = 58;
+pub const MAXICODE_LATCH: u8 = 63;
+
+pub static MAXICODE_SET_A: phf::Map<char, u8> = phf_map! {
+    '\r' => 0, 'A' => 1, 'B' => 2, 'C' => 3, 'D' => 4, 'E' => 5, 'F' => 6, 'G' => 7, 'H' => 8,
+    'I' => 9, 'J' => 10, 'K' => 11, 'L' => 12, 'M' => 13, 'N' => 14, 'O' => 15, 'P' => 16,
+    'Q' => 17, 'R' => 18, 'S' => 19, 'T' => 20, 'U' => 21, 'V' => 22, 'W' => 23, 'X' => 24,
+    'Y' => 25, 'Z' => 26, '\u{1c}' => 28, '\u{1d}' => 29, '\u{1e}' => 30, ' ' => 32,
+    '"' => 34, '#' => 35, '$' => 36, '%' => 37, '&' => 38, '\'' => 39, '(' => 40, ')' => 41,
+    '*' => 42, '+' => 43, ',' => 44, '-' => 45, '.' => 46, '/' => 47, '0' => 48, '1' => 49,
+    '2' => 50, '3' => 51, '4' => 52, '5' => 53, '6' => 54, '7' => 55, '8' => 56, '9' => 57,
+    ':' => 58
+};
+
+pub static MAXICODE_SET_B: phf::Map<char, u8> = phf_map! {
+    '`' => 0, 'a' => 1, 'b' => 2, 'c' => 3, 'd' => 4, 'e' => 5, 'f' => 6, 'g' => 7, 'h' => 8,
+    'i' => 9, 'j' => 10, 'k' => 11, 'l' => 12, 'm' => 13, 'n' => 14, 'o' => 15, 'p' => 16,
+    'q' => 17, 'r' => 18, 's' => 19, 't' => 20, 'u' => 21, 'v' => 22, 'w' => 23, 'x' => 24,
+    'y' => 25, 'z' => 26, '\u{1c}' => 28, '\u{1d}' => 29, '\u{1e}' => 30, '{' => 32,
+    '}' => 34, '~' => 35, '\u{7f}' => 36, ';' => 37, '<' => 38, '=' => 39, '>' => 40,
+    '?' => 41, '[' => 42, '\\' => 43, ']' => 44, '^' => 45, '_' => 46, ' ' => 47, ',' => 48,
+    '.' => 49, '/' => 50, ':' => 51, '@' => 52, '!' => 53, '|' => 54
+};
+
+pub static MAXICODE_SET_C: phf::Map<char, u8> = phf_map! {
+    'À' => 0, 'Á' => 1, 'Â' => 2, 'Ã' => 3, 'Ä' => 4, 'Å' => 5, 'Æ' => 6, 'Ç' => 7, 'È' => 8,
+    'É' => 9, 'Ê' => 10, 'Ë' => 11, 'Ì' => 12, 'Í' => 13, 'Î' => 14, 'Ï' => 15, 'Ð' => 16,
+    'Ñ' => 17, 'Ò' => 18, 'Ó' => 19, 'Ô' => 20, 'Õ' => 21, 'Ö' => 22, '×' => 23, 'Ø' => 24,
+    'Ù' => 25, 'Ú' => 26, '\u{1c}' => 28, '\u{1d}' => 29, '\u{1e}' => 30, 'Û' => 32,
+    'Ü' => 33, 'Ý' => 34, 'Þ' => 35, 'ß' => 36, 'ª' => 37, '¬' => 38, '±' => 39, '²' => 40,
+    '³' => 41, 'µ' => 42, '¹' => 43, 'º' => 44, '¼' => 45, '½' => 46, '¾' => 47,
+    '\u{80}' => 48, '\u{81}' => 49, '\u{82}' => 50, '\u{83}' => 51, '\u{84}' => 52,
+    '\u{85}' => 53, '\u{86}' => 54, '\u{87}' => 55, '\u{88}' => 56, '\u{89}' => 57, ' ' => 59
+};
+
+pub static MAXICODE_SET_D: phf::Map<char, u8> = phf_map! {
+    'à' => 0, 'á' => 1, 'â' => 2, 'ã' => 3, 'ä' => 4, 'å' => 5, 'æ' => 6, 'ç' => 7, 'è' => 8,
+    'é' => 9, 'ê' => 10, 'ë' => 11, 'ì' => 12, 'í' => 13, 'î' => 14, 'ï' => 15, 'ð' => 16,
+    'ñ' => 17, 'ò' => 18, 'ó' => 19, 'ô' => 20, 'õ' => 21, 'ö' => 22, '÷' => 23, 'ø' => 24,
+    'ù' => 25, 'ú' => 26, '\u{1c}' => 28, '\u{1d}' => 29, '\u{1e}' => 30, 'û' => 32,
+    'ü' => 33, 'ý' => 34, 'þ' => 35, 'ÿ' => 36, '¡' => 37, '¨' => 38, '«' => 39, '¯' => 40,
+    '°' => 41, '´' => 42, '·' => 43, '¸' => 44, '»' => 45, '¿' => 46, '\u{8a}' => 47,
+    '\u{8b}' => 48, '\u{8c}' => 49, '\u{8d}' => 50, '\u{8e}' => 51, '\u{8f}' => 52,
+    '\u{90}' => 53, '\u{91}' => 54, '\u{92}' => 55, '\u{93}' => 56, '\u{94}' => 57, ' ' => 59
+};
+
+pub static MAXICODE_SET_E: phf::Map<char, u8> = phf_map! {
+    '\u{00}' => 0, '\u{01}' => 1, '\u{02}' => 2, '\u{03}' => 3, '\u{04}' => 4, '\u{05}' => 5,
+    '\u{06}' => 6, '\u{07}' => 7, '\u{08}' => 8, '\u{09}' => 9, '\u{0a}' => 10, '\u{0b}' => 11,
+    '\u{0c}' => 12, '\u{0d}' => 13, '\u{0e}' => 14, '\u{0f}' => 15, '\u{10}' => 16,
+    '\u{11}' => 17, '\u{12}' => 18, '\u{13}' => 19, '\u{14}' => 20, '\u{15}' => 21,
+    '\u{16}' => 22, '\u{17}' => 23, '\u{18}' => 24, '\u{19}' => 25, '\u{1a}' => 26,
+    '\u{1b}' => 30, '\u{1c}' => 32, '\u{1d}' => 33, '\u{1e}' => 34, '\u{1f}' => 35,
+    '\u{9f}' => 36, '\u{a0}' => 37, '¢' => 38, '£' => 39, '¤' => 40, '¥' => 41, '¦' => 42,
+    '§' => 43, '©' => 44, '\u{ad}' => 45, '®' => 46, '¶' => 47, '\u{95}' => 48, '\u{96}' => 49,
+    '\u{97}' => 50, '\u{98}' => 51, '\u{99}' => 52, '\u{9a}' => 53, '\u{9b}' => 54,
+    '\u{9c}' => 55, '\u{9d}' => 56, '\u{9e}' => 57, ' ' => 59
+};
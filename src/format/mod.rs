@@ -1,15 +1,57 @@
+mod australia_post;
+mod channel_code;
 mod code_11;
+mod code_128_char_set;
+mod code_16k;
+mod code_39_char_set;
+mod code_49;
+mod code_49_tables;
 mod common_2_of_5_char_set;
+mod composite;
+mod composite_tables;
+mod databar;
+mod databar_expanded;
+mod databar_tables;
+mod dotcode;
 mod ean13;
 mod ean8;
+mod ean_add_on;
 mod ean_char_set;
+mod element_widths;
+mod gs1;
+mod gs1_128;
+mod hanxin;
+mod hanxin_tables;
+mod hibc;
+mod intelligent_mail;
 mod interleaved_2_of_5;
+mod japan_post;
+mod maxicode;
+mod maxicode_char_set;
+mod pharmacode;
+mod pharmacy;
+mod postnet;
+mod reed_solomon;
+mod royal_mail;
 mod standard_2_of_5;
+mod telepen;
 
+pub(crate) use code_128_char_set::*;
+pub(crate) use code_39_char_set::*;
+pub(crate) use code_49_tables::*;
 pub(crate) use common_2_of_5_char_set::*;
+pub(crate) use composite_tables::*;
+pub(crate) use databar::*;
+pub(crate) use databar_expanded::*;
+pub(crate) use databar_tables::*;
 pub(crate) use ean_char_set::*;
+pub(crate) use gs1::*;
+pub(crate) use gs1_128::*;
+pub(crate) use hanxin_tables::*;
+pub(crate) use maxicode_char_set::*;
+pub(crate) use reed_solomon::*;
 
-use crate::EncodingError;
+use crate::{EncodingError, StackedRow, Symbol};
 use bit_vec::BitVec;
 
 /// Encoding trait for fixed sized encodings
@@ -24,6 +66,26 @@ pub trait DynamicBarcodeEncoding: private::Sealed {
     fn encode(chars: &[char]) -> Result<BitVec, EncodingError>;
 }
 
+/// Append the `len` low bits of `value`, most significant first
+pub(crate) fn push_bits(bits: &mut Vec<bool>, value: u32, len: usize) {
+    bits.extend((0..len).rev().map(|i| value >> i & 1 == 1));
+}
+
+/// Rows of the given height bound by one module high separator bars above, between and below
+/// them
+pub(crate) fn bound_rows(rows: Vec<BitVec>, height: usize) -> Symbol {
+    let width = rows.first().map_or(0, BitVec::len);
+    let separator = || StackedRow {
+        modules: BitVec::from_elem(width, true),
+        height: 1,
+    };
+    let mut stacked = vec![separator()];
+    for modules in rows {
+        stacked.extend([StackedRow { modules, height }, separator()]);
+    }
+    Symbol::Stacked(stacked)
+}
+
 mod private {
     use super::*;
     pub trait Sealed {}
@@ -34,8 +96,28 @@ mod private {
     impl Sealed for Standard2Of5 {}
 }
 
+pub use australia_post::{AustraliaPost, AustraliaPostFormat};
+pub use channel_code::ChannelCode;
 pub use code_11::Code11;
+pub use code_16k::Code16K;
+pub use code_49::Code49;
+pub use composite::{Composite, CompositeComponent, CompositeLinear};
+pub use databar::{DataBar, DataBarLayout, DataBarLimited};
+pub use databar_expanded::DataBarExpanded;
+pub use dotcode::{DotCode, DotCodeSymbol};
 pub use ean13::EAN13;
 pub use ean8::EAN8;
+pub use ean_add_on::EanAddOn;
+pub use element_widths::{ElementWidths, Fraction};
+pub use hanxin::{HanXin, HanXinEccLevel, HanXinSymbol};
+pub use hibc::{Hibc, HibcSymbology};
+pub use intelligent_mail::IntelligentMail;
 pub use interleaved_2_of_5::Interleaved2Of5;
+pub use japan_post::JapanPost;
+pub use maxicode::{Bullseye, CarrierMessage, MaxiCode, MaxiCodeMode, MaxiCodeSymbol};
+pub use pharmacode::{Pharmacode, PharmacodeWidths};
+pub use pharmacy::{Code32, Pzn};
+pub use postnet::{Planet, Postnet};
+pub use royal_mail::{Kix, Mailmark, Rm4scc};
 pub use standard_2_of_5::Standard2Of5;
+pub use telepen::Telepen;
@@ -0,0 +1,158 @@
+use bit_vec::BitVec;
+
+use crate::{EncodingError, StackedRow, Symbol};
+
+/// Laetus Pharmacode format, an integer in narrow and wide bars on one track or in bars of
+/// three heights on two tracks
+pub struct Pharmacode;
+
+/// Widths of the [`Pharmacode`] bars and of the spaces between them, in modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PharmacodeWidths {
+    /// Width of the narrow bars, and of every two-track bar
+    pub narrow: usize,
+    /// Width of the wide bars
+    pub wide: usize,
+    /// Width of the spaces
+    pub space: usize,
+}
+
+impl PharmacodeWidths {
+    /// The 0.5 mm narrow bars, 1.5 mm wide bars and 1 mm spaces of the one-track standard
+    pub const ONE_TRACK: Self = Self {
+        narrow: 1,
+        wide: 3,
+        space: 2,
+    };
+    /// The 1 mm bars and spaces of the two-track standard
+    pub const TWO_TRACK: Self = Self {
+        narrow: 1,
+        wide: 1,
+        space: 1,
+    };
+}
+
+impl Default for PharmacodeWidths {
+    fn default() -> Self {
+        Self::ONE_TRACK
+    }
+}
+
+/// Height of each of the two tracks, in modules
+const TRACK_HEIGHT: usize = 4;
+
+impl Pharmacode {
+    /// Encode a value from 3 to 131070 in one track, as a [`Symbol::Linear`]
+    pub fn encode(widths: PharmacodeWidths, value: u32) -> Result<Symbol, EncodingError> {
+        check_range(value, 3, 131070)?;
+
+        // wide bars have weight 2 and narrow ones 1, the rightmost bar weighting the least
+        let mut wide = Vec::new();
+        let mut rest = value;
+        while rest != 0 {
+            let bar = if rest.is_multiple_of(2) { 2 } else { 1 };
+            wide.push(bar == 2);
+            rest = (rest - bar) / 2;
+        }
+
+        let mut modules = BitVec::new();
+        for (i, &wide) in wide.iter().rev().enumerate() {
+            if i > 0 {
+                modules.grow(widths.space, false);
+            }
+            modules.grow(if wide { widths.wide } else { widths.narrow }, true);
+        }
+        Ok(Symbol::Linear(modules))
+    }
+
+    /// Encode a value from 4 to 64570080 in two tracks, as a [`Symbol::Stacked`] of the upper
+    /// and lower track
+    pub fn encode_two_track(widths: PharmacodeWidths, value: u32) -> Result<Symbol, EncodingError> {
+        check_range(value, 4, 64570080)?;
+
+        // full bars have weight 3, upper ones 2 and lower ones 1
+        let mut bars = Vec::new();
+        let mut rest = value;
+        while rest != 0 {
+            let bar = match rest % 3 {
+                0 => 3,
+                bar => bar,
+            };
+            bars.push(bar);
+            rest = (rest - bar) / 3;
+        }
+
+        let mut rows = [BitVec::new(), BitVec::new()];
+        for (i, &bar) in bars.iter().rev().enumerate() {
+            for (track, row) in rows.iter_mut().enumerate() {
+                if i > 0 {
+                    row.grow(widths.space, false);
+                }
+                row.grow(widths.narrow, bar & (2 >> track) != 0);
+            }
+        }
+        Ok(Symbol::Stacked(
+            rows.into_iter()
+                .map(|modules| StackedRow {
+                    modules,
+                    height: TRACK_HEIGHT,
+                })
+                .collect(),
+        ))
+    }
+
+    /// Value of one-track bars, a bar at least halfway between the narrow and wide widths
+    /// reading as wide
+    pub fn decode(widths: PharmacodeWidths, modules: &BitVec) -> Option<u32> {
+        let mut value = 0u32;
+        let mut run = 0;
+        for dark in modules.iter().chain([false]) {
+            if dark {
+                run += 1;
+            } else if run > 0 {
+                let bar = if 2 * run >= widths.narrow + widths.wide {
+                    2
+                } else {
+                    1
+                };
+                value = value.checked_mul(2)?.checked_add(bar)?;
+                run = 0;
+            }
+        }
+        (value != 0).then_some(value)
+    }
+
+    /// Value of a two-track [`Symbol::Stacked`], as returned by [`Pharmacode::encode_two_track`]
+    pub fn decode_two_track(symbol: &Symbol) -> Option<u32> {
+        let Symbol::Stacked(rows) = symbol else {
+            return None;
+        };
+        let [upper, lower] = &rows[..] else {
+            return None;
+        };
+
+        let mut value = 0u32;
+        let mut bar = 0;
+        let columns = upper.modules.iter().zip(lower.modules.iter());
+        for (upper, lower) in columns.chain([(false, false)]) {
+            match (upper, lower) {
+                (false, false) if bar != 0 => {
+                    value = value.checked_mul(3)?.checked_add(bar)?;
+                    bar = 0;
+                }
+                (upper, lower) => bar |= (upper as u32) << 1 | lower as u32,
+            }
+        }
+        (value != 0).then_some(value)
+    }
+}
+
+fn check_range(value: u32, min: u32, max: u32) -> Result<(), EncodingError> {
+    if value < min {
+        Err(EncodingError::WrongSize)
+    } else if value > max {
+        Err(EncodingError::TooLong)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{EncodingError, Symbol};
+
+use super::code_39_modules;
+
+/// German Pharmazentralnummer format, the PZN behind a `-` in Code 39 with wide elements three
+/// times as wide as narrow ones
+pub struct Pzn;
+
+/// Italian Code 32 format, the pharmaceutical AIC number converted to base 32 and rendered in
+/// Code 39 with wide elements three times as wide as narrow ones
+pub struct Code32;
+
+/// Digits of base 32 Code 32 characters
+const CODE_32_CHARS: &str = "0123456789BCDFGHJKLMNPQRSTUVWXYZ";
+
+impl Pzn {
+    /// Encode a PZN8 as a [`Symbol::Linear`], the 7 digits of the product number being
+    /// followed by their mod 11 check digit.
+    ///
+    /// Shorter numbers are padded with leading zeros, and a number of 8 digits must end with
+    /// its check digit. Numbers whose check digit would be 10 are not issued and are rejected.
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        Ok(Symbol::Linear(code_39_modules(&Self::chars(data, 7)?)?))
+    }
+
+    /// Encode an old PZN7 as a [`Symbol::Linear`], the 6 digits of the product number being
+    /// followed by their mod 11 check digit, as with [`Pzn::encode`]
+    pub fn encode_pzn7(data: &[char]) -> Result<Symbol, EncodingError> {
+        Ok(Symbol::Linear(code_39_modules(&Self::chars(data, 6)?)?))
+    }
+
+    /// Code 39 characters of a product number of `len` digits, the `-` and its digits
+    /// followed by the check digit
+    pub(crate) fn chars(data: &[char], len: usize) -> Result<Vec<char>, EncodingError> {
+        let (mut digits, check) = product_digits(data, len)?;
+
+        // PZN8 digits are weighted from 1 and PZN7 ones from 2
+        let sum = digits
+            .iter()
+            .zip(8 - len..)
+            .map(|(&digit, weight)| digit * weight as u32)
+            .sum::<u32>();
+        let computed = sum % 11;
+        if computed == 10 || check.is_some_and(|check| check != computed) {
+            return Err(EncodingError::WrongCheckusm);
+        }
+        digits.push(computed);
+
+        let mut chars = vec!['-'];
+        chars.extend(digits.iter().filter_map(|&d| char::from_digit(d, 10)));
+        Ok(chars)
+    }
+}
+
+impl Code32 {
+    /// Encode an AIC number as a [`Symbol::Linear`], its 8 digits followed by their check
+    /// digit being written as 6 base 32 characters.
+    ///
+    /// Shorter numbers are padded with leading zeros, and a number of 9 digits must end with
+    /// its check digit.
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        Ok(Symbol::Linear(code_39_modules(&Self::chars(data)?)?))
+    }
+
+    /// Code 39 characters of an AIC number, its 6 base 32 characters
+    pub(crate) fn chars(data: &[char]) -> Result<Vec<char>, EncodingError> {
+        let (digits, check) = product_digits(data, 8)?;
+
+        // digits in even positions are doubled, adding the digits of the product
+        let sum = digits
+            .iter()
+            .enumerate()
+            .map(|(i, &digit)| {
+                if i % 2 == 0 {
+                    digit
+                } else {
+                    2 * digit / 10 + 2 * digit % 10
+                }
+            })
+            .sum::<u32>();
+        let computed = sum % 10;
+        if check.is_some_and(|check| check != computed) {
+            return Err(EncodingError::WrongCheckusm);
+        }
+
+        let mut value = digits
+            .iter()
+            .chain([&computed])
+            .fold(0, |acc, d| acc * 10 + d);
+        let mut chars = vec!['0'; 6];
+        for c in chars.iter_mut().rev() {
+            *c = CODE_32_CHARS.as_bytes()[value as usize % 32] as char;
+            value /= 32;
+        }
+        Ok(chars)
+    }
+}
+
+/// The `len` digits of a product number padded with leading zeros, and the check digit
+/// following them if given
+fn product_digits(data: &[char], len: usize) -> Result<(Vec<u32>, Option<u32>), EncodingError> {
+    if data.len() > len + 1 {
+        return Err(EncodingError::TooLong);
+    }
+    let mut digits = data
+        .iter()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(EncodingError::WrongChar)?;
+    let check = if digits.len() == len + 1 {
+        digits.pop()
+    } else {
+        None
+    };
+    digits.splice(0..0, std::iter::repeat_n(0, len - digits.len()));
+    Ok((digits, check))
+}
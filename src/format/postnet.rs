@@ -0,0 +1,59 @@
+use crate::{EncodingError, Symbol, TwoStateBar};
+
+/// USPS POSTNET format, the ZIP, ZIP+4 or delivery point code in height-modulated bars
+pub struct Postnet;
+
+/// USPS PLANET format, the inverse of [`Postnet`] used for tracking
+pub struct Planet;
+
+/// Tall bars of each POSTNET digit, the first bar in the highest bit, PLANET digits using the
+/// opposite heights
+const POSTNET_DIGITS: [u8; 10] = [
+    0b11000, 0b00011, 0b00101, 0b00110, 0b01001, 0b01010, 0b01100, 0b10001, 0b10010, 0b10100,
+];
+
+impl Postnet {
+    /// Encode the 5, 9 or 11 digits followed by their correction digit as [`Symbol::TwoState`]
+    /// bars
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        if !matches!(data.len(), 5 | 9 | 11) {
+            return Err(EncodingError::WrongSize);
+        }
+        encode(data, false)
+    }
+}
+
+impl Planet {
+    /// Encode the 11 or 13 digits followed by their correction digit as [`Symbol::TwoState`]
+    /// bars
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        if !matches!(data.len(), 11 | 13) {
+            return Err(EncodingError::WrongSize);
+        }
+        encode(data, true)
+    }
+}
+
+/// Frame bars around the digits and their mod 10 correction digit
+fn encode(data: &[char], planet: bool) -> Result<Symbol, EncodingError> {
+    let mut digits = data
+        .iter()
+        .map(|c| c.to_digit(10).map(|d| d as u8))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(EncodingError::WrongChar)?;
+    let sum = digits.iter().map(|&d| d as usize).sum::<usize>();
+    digits.push(((10 - sum % 10) % 10) as u8);
+
+    let mut bars = vec![TwoStateBar::Tall];
+    for digit in digits {
+        bars.extend((0..5).rev().map(|i| {
+            if (POSTNET_DIGITS[digit as usize] >> i & 1 == 1) != planet {
+                TwoStateBar::Tall
+            } else {
+                TwoStateBar::Short
+            }
+        }));
+    }
+    bars.push(TwoStateBar::Tall);
+    Ok(Symbol::TwoState(bars))
+}
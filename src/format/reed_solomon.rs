@@ -0,0 +1,60 @@
+/// Galois field GF(2^m) used for the Reed–Solomon error correction of the matrix formats
+pub struct GaloisField {
+    exp: Vec<usize>,
+    log: Vec<usize>,
+    size: usize,
+}
+
+impl GaloisField {
+    /// Build the field of `2^bits` elements from its primitive polynomial
+    pub fn new(bits: u32, primitive: usize) -> Self {
+        let size = 1 << bits;
+        let mut exp = vec![0; size];
+        let mut log = vec![0; size];
+        let mut value = 1;
+        for (i, e) in exp.iter_mut().enumerate().take(size - 1) {
+            *e = value;
+            log[value] = i;
+            value <<= 1;
+            if value >= size {
+                value ^= primitive;
+            }
+        }
+        Self { exp, log, size }
+    }
+
+    fn mul(&self, a: usize, b: usize) -> usize {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[(self.log[a] + self.log[b]) % (self.size - 1)]
+    }
+
+    /// Compute `ecc_len` check codewords for `data`, the generator polynomial
+    /// having roots `α^first_root .. α^(first_root + ecc_len - 1)`.
+    ///
+    /// The check codewords are returned highest degree first, in transmission order.
+    pub fn encode(&self, data: &[u8], ecc_len: usize, first_root: usize) -> Vec<u8> {
+        let mut generator = vec![1];
+        for i in 0..ecc_len {
+            let root = self.exp[(first_root + i) % (self.size - 1)];
+            let mut next = vec![0; generator.len() + 1];
+            for (j, &coefficient) in generator.iter().enumerate() {
+                next[j] ^= coefficient;
+                next[j + 1] ^= self.mul(coefficient, root);
+            }
+            generator = next;
+        }
+
+        let mut remainder = vec![0; ecc_len];
+        for &d in data {
+            let factor = d as usize ^ remainder[0];
+            remainder.rotate_left(1);
+            remainder[ecc_len - 1] = 0;
+            for (r, &g) in remainder.iter_mut().zip(generator[1..].iter()) {
+                *r ^= self.mul(factor, g);
+            }
+        }
+        remainder.into_iter().map(|r| r as u8).collect()
+    }
+}
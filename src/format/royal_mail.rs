@@ -0,0 +1,231 @@
+use super::GaloisField;
+use crate::{EncodingError, FourStateBar, Symbol};
+
+/// Royal Mail 4-State Customer Code format, the postcode and delivery point suffix followed by
+/// a row and column check character
+pub struct Rm4scc;
+
+/// Dutch KIX format, the RM4SCC characters without start, stop and check characters
+pub struct Kix;
+
+/// Royal Mail Mailmark 4-state format, barcode C or L
+pub struct Mailmark;
+
+/// Character set of RM4SCC and KIX, the character at row `r` and column `c` having index
+/// `6 * r + c`
+const ROYAL_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Two of the four bars of a character having an ascender by row, and a descender by column,
+/// the first bar in the highest bit
+const ROYAL_HALVES: [u8; 6] = [0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100];
+
+/// Letters of the alphabetic (`A`) and limited alphabetic (`L`) postcode positions
+const MAILMARK_ALPHA: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const MAILMARK_LIMITED: &str = "ABDEFGHJLNPQRSTUWXYZ";
+
+/// Postcode and DPS layouts, `A` for a letter, `L` for a limited letter, `N` for a digit and
+/// `S` for a space
+const MAILMARK_POSTCODES: [&str; 6] = [
+    "ANANLLNLS",
+    "AANNLLNLS",
+    "AANNNLLNL",
+    "AANANLLNL",
+    "ANNLLNLSS",
+    "ANNNLLNLS",
+];
+
+/// Postcode of international mail, encoded as 0
+const MAILMARK_INTERNATIONAL: &str = "XY11     ";
+
+/// 6 bit symbols of odd parity, for the data numbers in base 32 and the check numbers, and of
+/// even parity, for the data numbers in base 30
+const MAILMARK_ODD: [u8; 32] = [
+    0x01, 0x02, 0x04, 0x07, 0x08, 0x0B, 0x0D, 0x0E, 0x10, 0x13, 0x15, 0x16, 0x19, 0x1A, 0x1C, 0x1F,
+    0x20, 0x23, 0x25, 0x26, 0x29, 0x2A, 0x2C, 0x2F, 0x31, 0x32, 0x34, 0x37, 0x38, 0x3B, 0x3D, 0x3E,
+];
+const MAILMARK_EVEN: [u8; 30] = [
+    0x03, 0x05, 0x06, 0x09, 0x0A, 0x0C, 0x0F, 0x11, 0x12, 0x14, 0x17, 0x18, 0x1B, 0x1D, 0x1E, 0x21,
+    0x22, 0x24, 0x27, 0x28, 0x2B, 0x2D, 0x2E, 0x30, 0x33, 0x35, 0x36, 0x39, 0x3A, 0x3C,
+];
+
+/// Extender group of each symbol, for barcode C and barcode L
+const MAILMARK_EXTENDERS_C: [u8; 22] = [
+    3, 5, 7, 11, 13, 14, 16, 17, 19, 0, 1, 2, 4, 6, 8, 9, 10, 12, 15, 18, 20, 21,
+];
+const MAILMARK_EXTENDERS_L: [u8; 26] = [
+    2, 5, 7, 8, 13, 14, 15, 16, 21, 22, 23, 0, 1, 3, 4, 6, 9, 10, 11, 12, 17, 18, 19, 20, 24, 25,
+];
+
+impl Rm4scc {
+    /// Encode the postcode and DPS characters, digits and letters, as [`Symbol::FourState`]
+    /// bars between the start and stop bars
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        let indices = royal_indices(data)?;
+        let (top, bottom) = indices.iter().fold((0, 0), |(top, bottom), &i| {
+            (top + (i / 6 + 1) % 6, bottom + (i % 6 + 1) % 6)
+        });
+        let check = 6 * ((top + 5) % 6) + (bottom + 5) % 6;
+
+        let mut bars = vec![FourStateBar::Ascender];
+        for index in indices.into_iter().chain([check]) {
+            bars.extend(royal_bars(index));
+        }
+        bars.push(FourStateBar::Full);
+        Ok(Symbol::FourState(bars))
+    }
+}
+
+impl Kix {
+    /// Encode the postcode, house number and suffix characters, digits and letters, as
+    /// [`Symbol::FourState`] bars
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        Ok(Symbol::FourState(
+            royal_indices(data)?
+                .into_iter()
+                .flat_map(royal_bars)
+                .collect(),
+        ))
+    }
+}
+
+/// Indices in [`ROYAL_CHARS`], lower case letters standing for upper case ones
+fn royal_indices(data: &[char]) -> Result<Vec<usize>, EncodingError> {
+    data.iter()
+        .map(|c| ROYAL_CHARS.find(c.to_ascii_uppercase()))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(EncodingError::WrongChar)
+}
+
+fn royal_bars(index: usize) -> impl Iterator<Item = FourStateBar> {
+    let (ascenders, descenders) = (ROYAL_HALVES[index / 6], ROYAL_HALVES[index % 6]);
+    (0..4)
+        .rev()
+        .map(move |i| four_state(ascenders >> i & 1 == 1, descenders >> i & 1 == 1))
+}
+
+fn four_state(ascender: bool, descender: bool) -> FourStateBar {
+    match (ascender, descender) {
+        (true, true) => FourStateBar::Full,
+        (true, false) => FourStateBar::Ascender,
+        (false, true) => FourStateBar::Descender,
+        (false, false) => FourStateBar::Tracker,
+    }
+}
+
+impl Mailmark {
+    /// Encode the format, version ID, class, supply chain ID, item ID and destination postcode
+    /// plus DPS as [`Symbol::FourState`] bars.
+    ///
+    /// A 2 digit supply chain ID gives barcode C of 22 characters and a 6 digit one barcode L of
+    /// 26 characters, the trailing spaces of the postcode being optional.
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        let mut data = data
+            .iter()
+            .map(char::to_ascii_uppercase)
+            .collect::<Vec<_>>();
+        let len = match data.len() {
+            14..=22 => 22,
+            23..=26 => 26,
+            _ => return Err(EncodingError::WrongSize),
+        };
+        data.resize(len, ' ');
+
+        let digit = |c: char| c.to_digit(10).map(u128::from);
+        let number = |chars: &[char]| {
+            chars
+                .iter()
+                .try_fold(0, |acc, &c| Some(acc * 10 + digit(c)?))
+                .ok_or(EncodingError::WrongChar)
+        };
+        let format = digit(data[0])
+            .filter(|&d| d <= 4)
+            .ok_or(EncodingError::WrongChar)?;
+        let version = digit(data[1])
+            .filter(|d| (1..=4).contains(d))
+            .ok_or(EncodingError::WrongChar)?;
+        let class = data[2]
+            .to_digit(15)
+            .map(u128::from)
+            .ok_or(EncodingError::WrongChar)?;
+        let supply_chain = number(&data[3..len - 17])?;
+        let item = number(&data[len - 17..len - 9])?;
+        let postcode = mailmark_postcode(&data[len - 9..])?;
+
+        let mut value = (postcode * 100_000_000 + item) * if len == 22 { 100 } else { 1_000_000 }
+            + supply_chain;
+        value = ((value * 15 + class) * 5 + format) * 4 + version - 1;
+
+        // data numbers, the last ones in base 32 and the first ones in base 30
+        let (data_len, base_30) = if len == 22 { (16, 9) } else { (19, 11) };
+        let mut numbers = vec![0u8; data_len];
+        for (i, number) in numbers.iter_mut().enumerate().rev() {
+            let base = if i < base_30 { 30 } else { 32 };
+            *number = (value % base) as u8;
+            value /= base;
+        }
+        let check = GaloisField::new(5, 0x25).encode(&numbers, len - data_len, 1);
+
+        let mut extenders = vec![0; len];
+        let groups = if len == 22 {
+            &MAILMARK_EXTENDERS_C[..]
+        } else {
+            &MAILMARK_EXTENDERS_L[..]
+        };
+        for (i, number) in numbers.into_iter().chain(check).enumerate() {
+            extenders[groups[i] as usize] = if i < base_30 {
+                MAILMARK_EVEN[number as usize]
+            } else {
+                MAILMARK_ODD[number as usize]
+            };
+        }
+
+        // three bars per extender, from its upper and lower three bits, the bars of the odd
+        // extenders being upside down
+        let mut bars = Vec::with_capacity(3 * len);
+        for (i, extender) in extenders.into_iter().enumerate() {
+            for bit in (0..3).rev() {
+                let (upper, lower) = (extender >> (bit + 3) & 1 == 1, extender >> bit & 1 == 1);
+                bars.push(if i % 2 == 0 {
+                    four_state(upper, lower)
+                } else {
+                    four_state(lower, upper)
+                });
+            }
+        }
+        Ok(Symbol::FourState(bars))
+    }
+}
+
+/// Value of the destination postcode plus DPS, following the layout it matches
+fn mailmark_postcode(postcode: &[char]) -> Result<u128, EncodingError> {
+    if postcode.iter().copied().eq(MAILMARK_INTERNATIONAL.chars()) {
+        return Ok(0);
+    }
+
+    // each layout starting after the values of the previous ones
+    let mut offset = 1;
+    for layout in MAILMARK_POSTCODES {
+        let value = layout
+            .chars()
+            .zip(postcode)
+            .try_fold(0u128, |acc, (kind, &c)| match kind {
+                'A' => Some(acc * 26 + MAILMARK_ALPHA.find(c)? as u128),
+                'L' => Some(acc * 20 + MAILMARK_LIMITED.find(c)? as u128),
+                'N' => Some(acc * 10 + c.to_digit(10)? as u128),
+                _ => (c == ' ').then_some(acc),
+            });
+        if let Some(value) = value {
+            return Ok(offset + value);
+        }
+        offset += layout
+            .chars()
+            .map(|kind| match kind {
+                'A' => 26,
+                'L' => 20,
+                'N' => 10,
+                _ => 1,
+            })
+            .product::<u128>();
+    }
+    Err(EncodingError::WrongChar)
+}
@@ -1,27 +1,47 @@
-use bit_vec::BitVec;
+use crate::{format::WIDTH_MAP, Elements, EncodingError};
 
-use crate::{format::WIDTH_MAP, EncodingError};
-
-use super::DynamicBarcodeEncoding;
+use super::{DynamicBarcodeEncoding, ElementWidths, Width};
 
 /// Standard 2 of 5 format, for more [`info`](https://web.archive.org/web/20070202214456/http://barcodeisland.com/2of5.phtml)
 pub struct Standard2Of5;
 
-impl DynamicBarcodeEncoding for Standard2Of5 {
-    fn encode(chars: &[char]) -> Result<bit_vec::BitVec, crate::EncodingError> {
-        let mut ret = BitVec::new();
-        ret.extend([true, true, false, true, true, false, true, false]);
-        for ch in chars.iter() {
+/// Widths of the start and stop patterns, in modules
+const START: [usize; 6] = [2, 1, 2, 1, 1, 1];
+const STOP: [usize; 5] = [2, 1, 1, 1, 2];
+
+impl Standard2Of5 {
+    /// Wide bars of 3 modules and gaps of 1 module
+    pub const WIDTHS: ElementWidths = ElementWidths::whole(3);
+
+    /// Encode the provided digits as bars and spaces grouped by character, the start and stop
+    /// patterns included, with the given element widths, the gap being the last space of each
+    /// character.
+    ///
+    /// The start and stop patterns keep their 2 module wide bars.
+    pub fn elements(chars: &[char], widths: ElementWidths) -> Result<Elements, EncodingError> {
+        let (narrow, wide, gap) = widths.units()?;
+        let mut elements = Elements {
+            scale: narrow,
+            ..Default::default()
+        };
+        elements.push_character(START.map(|width| width * narrow));
+        for ch in chars {
             let width_map = WIDTH_MAP.get(ch).ok_or(EncodingError::WrongChar)?;
-            for i in width_map.iter() {
-                match i {
-                    crate::format::Width::Wide => ret.extend([true, true, true]),
-                    crate::format::Width::Narrow => ret.push(true),
-                }
-                ret.push(false);
-            }
+            elements.push_character(width_map.iter().enumerate().flat_map(|(i, width)| {
+                let bar = match width {
+                    Width::Wide => wide,
+                    Width::Narrow => narrow,
+                };
+                [bar, if i == 4 { gap } else { narrow }]
+            }));
         }
-        ret.extend([true, true, false, true, false, true, true]);
-        Ok(ret)
+        elements.push_character(STOP.map(|width| width * narrow));
+        Ok(elements)
+    }
+}
+
+impl DynamicBarcodeEncoding for Standard2Of5 {
+    fn encode(chars: &[char]) -> Result<bit_vec::BitVec, crate::EncodingError> {
+        Ok(Standard2Of5::elements(chars, Standard2Of5::WIDTHS)?.modules())
     }
 }
@@ -0,0 +1,103 @@
+use bit_vec::BitVec;
+
+use crate::{EncodingError, Symbol};
+
+/// Telepen format, full ASCII or numeric data followed by a modulo 127 check character
+pub struct Telepen;
+
+const START: u8 = b'_';
+const STOP: u8 = b'z';
+
+impl Telepen {
+    /// Encode ASCII data as a [`Symbol::Linear`]
+    pub fn encode(data: &[char]) -> Result<Symbol, EncodingError> {
+        let bytes = data
+            .iter()
+            .map(|&c| u8::try_from(c).ok().filter(u8::is_ascii))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(EncodingError::WrongChar)?;
+        Ok(Symbol::Linear(telepen_modules(&bytes)))
+    }
+
+    /// Encode digits as a [`Symbol::Linear`] of Telepen Numeric, each pair of digits taking a
+    /// character.
+    ///
+    /// A digit followed by `X` in place of the second digit of a pair stands alone, and data of
+    /// odd length gets a leading zero.
+    pub fn encode_numeric(data: &[char]) -> Result<Symbol, EncodingError> {
+        let mut data = data
+            .iter()
+            .map(char::to_ascii_uppercase)
+            .collect::<Vec<_>>();
+        if data.len() % 2 == 1 {
+            data.insert(0, '0');
+        }
+
+        let bytes = data
+            .chunks(2)
+            .map(|pair| match (pair[0].to_digit(10), pair[1]) {
+                (Some(digit), 'X') => Some(digit as u8 + 17),
+                (Some(digit), c) => Some((digit * 10 + c.to_digit(10)?) as u8 + 27),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(EncodingError::WrongChar)?;
+        Ok(Symbol::Linear(telepen_modules(&bytes)))
+    }
+}
+
+/// Modules of the start character, the characters, their check character and the stop
+/// character
+fn telepen_modules(bytes: &[u8]) -> BitVec {
+    let sum = bytes.iter().map(|&b| b as usize).sum::<usize>();
+    let check = ((127 - sum % 127) % 127) as u8;
+
+    let mut modules = BitVec::new();
+    for &byte in [START].iter().chain(bytes).chain(&[check, STOP]) {
+        push_character(&mut modules, byte);
+    }
+    modules
+}
+
+/// Bits of the character with its even parity bit, least significant first, as narrow and
+/// wide elements.
+///
+/// A 1 takes a narrow bar and a narrow space, and a pair of 0s a wide bar and a narrow space.
+/// The 0s around a single 1 take a wide bar and a wide space, and those around several 1s a
+/// narrow bar and a wide space each, with a narrow space and a narrow bar for the inner 1s.
+fn push_character(modules: &mut BitVec, byte: u8) {
+    const NARROW: usize = 1;
+    const WIDE: usize = 3;
+
+    let byte = byte | ((byte.count_ones() as u8 & 1) << 7);
+    let bit = |i: usize| byte >> i & 1 == 1;
+    let mut push = |widths: &[usize]| {
+        for (i, &width) in widths.iter().enumerate() {
+            modules.grow(width, i % 2 == 0);
+        }
+    };
+
+    let mut i = 0;
+    while i < 8 {
+        if bit(i) {
+            push(&[NARROW, NARROW]);
+            i += 1;
+        } else if !bit(i + 1) {
+            push(&[WIDE, NARROW]);
+            i += 2;
+        } else {
+            // even parity leaving an even number of 0s, this one is closed by a later 0
+            let ones = (i + 1..8).take_while(|&j| bit(j)).count();
+            if ones == 1 {
+                push(&[WIDE, WIDE]);
+            } else {
+                push(&[NARROW, WIDE]);
+                for _ in 0..ones - 2 {
+                    push(&[NARROW, NARROW]);
+                }
+                push(&[NARROW, WIDE]);
+            }
+            i += ones + 2;
+        }
+    }
+}
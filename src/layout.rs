@@ -0,0 +1,131 @@
+use std::ops::Range;
+
+use crate::{
+    format::{CompositeLinear, HibcSymbology},
+    BarcodeFormat, Symbol,
+};
+
+/// Light modules required left and right of a symbol, all around it for matrix symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QuietZone {
+    /// Light modules left of the symbol
+    pub left: usize,
+    /// Light modules right of the symbol
+    pub right: usize,
+}
+
+impl QuietZone {
+    /// Same quiet zone on both sides
+    pub const fn both(width: usize) -> Self {
+        Self {
+            left: width,
+            right: width,
+        }
+    }
+}
+
+/// Encoded symbol with its required quiet zone and the modules taken by its guard patterns
+/// and data characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    /// Encoded symbol, with the quiet zone when included, its modules being units of
+    /// [`Layout::scale`]
+    pub symbol: Symbol,
+    /// Units in a module of the symbol, the guards and the characters, 1 unless the element
+    /// widths are fractions of a module
+    pub scale: usize,
+    /// Quiet zone required by the symbology, in modules
+    pub quiet_zone: QuietZone,
+    /// Modules of the guard patterns of EAN symbols and of the EAN and UPC linear components
+    /// of composite symbols, extended below the data characters when the human readable text
+    /// is printed
+    pub guards: Vec<Range<usize>>,
+    /// Modules of the data and check characters of Code 11, 2 of 5 and EAN symbols, add-on
+    /// digits included, and of the EAN and UPC linear components of composite symbols.
+    ///
+    /// The guards and characters of other formats, PZN, Code 32, HIBC, Telepen and Channel
+    /// Code among them, are left empty.
+    pub characters: Vec<Range<usize>>,
+}
+
+impl BarcodeFormat {
+    /// Quiet zone required by the symbology, without add-on for EAN
+    pub fn quiet_zone(&self) -> QuietZone {
+        match self {
+            BarcodeFormat::EAN13 | BarcodeFormat::Composite(CompositeLinear::EAN13) => {
+                QuietZone { left: 11, right: 7 }
+            }
+            BarcodeFormat::EAN8 | BarcodeFormat::Composite(CompositeLinear::EAN8) => {
+                QuietZone::both(7)
+            }
+            BarcodeFormat::Composite(CompositeLinear::UPCA) => QuietZone::both(9),
+            BarcodeFormat::Composite(CompositeLinear::UPCE) => QuietZone { left: 9, right: 7 },
+            BarcodeFormat::Code11
+            | BarcodeFormat::Interleaved2Of5
+            | BarcodeFormat::Standard2Of5
+            | BarcodeFormat::Composite(CompositeLinear::GS1128)
+            | BarcodeFormat::AustraliaPost(_)
+            | BarcodeFormat::Pzn
+            | BarcodeFormat::Pzn7
+            | BarcodeFormat::Code32
+            | BarcodeFormat::Hibc(HibcSymbology::Code128 | HibcSymbology::Code39)
+            | BarcodeFormat::Telepen
+            | BarcodeFormat::TelepenNumeric => QuietZone::both(10),
+            BarcodeFormat::Code16K | BarcodeFormat::Code49 => QuietZone { left: 10, right: 1 },
+            BarcodeFormat::ChannelCode => QuietZone { left: 1, right: 2 },
+            BarcodeFormat::MaxiCode(_) => QuietZone::both(1),
+            BarcodeFormat::DotCode | BarcodeFormat::HanXin(_) => QuietZone::both(3),
+            BarcodeFormat::DataBar(_)
+            | BarcodeFormat::DataBarLimited
+            | BarcodeFormat::DataBarExpanded
+            | BarcodeFormat::DataBarExpandedStacked(_) => QuietZone::default(),
+            BarcodeFormat::IntelligentMail | BarcodeFormat::Postnet | BarcodeFormat::Planet => {
+                QuietZone::both(5)
+            }
+            BarcodeFormat::Rm4scc
+            | BarcodeFormat::Kix
+            | BarcodeFormat::Mailmark
+            | BarcodeFormat::JapanPost => QuietZone::both(4),
+            BarcodeFormat::Pharmacode | BarcodeFormat::PharmacodeTwoTrack => QuietZone::both(6),
+        }
+    }
+}
+
+/// Guard patterns and data characters of the EAN and UPC linear component, the last row of a
+/// composite symbol starting at its first bar, none for GS1-128
+pub(crate) fn composite_ranges(
+    linear: CompositeLinear,
+    symbol: &Symbol,
+) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let start = match symbol {
+        Symbol::Stacked(rows) => rows
+            .last()
+            .and_then(|row| row.modules.iter().position(|dark| dark)),
+        _ => None,
+    };
+    let (guards, characters): (&[Range<usize>], Vec<usize>) = match linear {
+        CompositeLinear::EAN13 | CompositeLinear::UPCA => (
+            &[0..3, 45..50, 92..95],
+            (3..45).step_by(7).chain((50..92).step_by(7)).collect(),
+        ),
+        CompositeLinear::EAN8 => (
+            &[0..3, 31..36, 64..67],
+            (3..31).step_by(7).chain((36..64).step_by(7)).collect(),
+        ),
+        CompositeLinear::UPCE => (&[0..3, 45..51], (3..45).step_by(7).collect()),
+        CompositeLinear::GS1128 => (&[], Vec::new()),
+    };
+    let Some(start) = start else {
+        return (Vec::new(), Vec::new());
+    };
+    (
+        guards
+            .iter()
+            .map(|guard| start + guard.start..start + guard.end)
+            .collect(),
+        characters
+            .into_iter()
+            .map(|character| start + character..start + character + 7)
+            .collect(),
+    )
+}
@@ -9,35 +9,95 @@
 //! - EAN8,
 //! - Interleaved 2 of 5,
 //! - Standard 2 of 5,
+//! - MaxiCode (modes 2 to 6, see [`format::MaxiCode`]),
+//! - DotCode (see [`format::DotCode`]),
+//! - Han Xin Code (see [`format::HanXin`]),
+//! - GS1 DataBar Omnidirectional, Truncated, Stacked and Stacked Omnidirectional
+//!   (see [`format::DataBar`]),
+//! - GS1 DataBar Limited (see [`format::DataBarLimited`]),
+//! - GS1 DataBar Expanded and Expanded Stacked (see [`format::DataBarExpanded`]),
+//! - GS1 Composite with CC-A, CC-B and CC-C over EAN-13, EAN-8, UPC-A, UPC-E and GS1-128
+//!   (see [`format::Composite`]),
+//! - USPS Intelligent Mail (see [`format::IntelligentMail`]),
+//! - USPS POSTNET and PLANET (see [`format::Postnet`] and [`format::Planet`]),
+//! - Royal Mail 4-State Customer Code, KIX and Royal Mail Mailmark (see [`format::Rm4scc`],
+//!   [`format::Kix`] and [`format::Mailmark`]),
+//! - Australia Post customer, reply paid, routing and redirection barcodes
+//!   (see [`format::AustraliaPost`]),
+//! - Japan Post customer barcode (see [`format::JapanPost`]),
+//! - Pharmacode one-track and two-track (see [`format::Pharmacode`]),
+//! - PZN8, PZN7 and Italian Code 32 (see [`format::Pzn`] and [`format::Code32`]),
+//! - HIBC LIC and PAS in Code 128 or Code 39 (see [`format::Hibc`]),
+//! - Telepen and Telepen Numeric (see [`format::Telepen`]),
+//! - Channel Code, Code 16K and Code 49 (see [`format::ChannelCode`], [`format::Code16K`] and
+//!   [`format::Code49`]),
+//!
+//! Linear formats encode to a single row of modules, the others to stacked rows, matrices,
+//! 4-state or 2-state bars, all of them returned as a [`Symbol`].
+//! Linear symbols also read as bars and spaces with their widths, grouped by character for
+//! Code 11, 2 of 5 and EAN (see [`Elements`]), Code 11 and 2 of 5 taking fractional wide to
+//! narrow ratios and gaps (see [`format::ElementWidths`]) laid out in fractions of a module by
+//! [`encode_layout`].
+//! [`encode_with`] takes the check characters, element widths, EAN add-on and quiet zone from
+//! [`EncodeOptions`], [`encode_layout`] also returning the quiet zone required by the
+//! symbology and the modules of the guard patterns and data characters (see [`Layout`]).
+//!
+//! Symbols are drawn with Unicode block characters by [`render::TerminalRenderer`], as EPS or
+//! PDF vector graphics by [`render::VectorRenderer`], as ZPL, EPL or DPL label printer commands
+//! by [`render::LabelRenderer`] and as ESC/POS receipt printer commands by
+//! [`render::EscPosRenderer`], the last three narrowing the bars by a
+//! [`render::BarWidthReduction`] to make up for ink spread.
 //!
 //! # Usage
 //!
 //! ```rust
+//! # use barcode_rs::{BarcodeFormat, BorrowEncodingSource, Symbol};
 //! let my_data = "7501031311309";
-//! let result: bit_vec::BitVec = my_data.barcode_encode(BarcodeFormat::EAN13).unwrap();
+//! let result: Symbol = my_data.barcode_encode(BarcodeFormat::EAN13).unwrap();
+//! let result: bit_vec::BitVec = result.try_into().unwrap();
 //!
-//! # assert_eq!(result, "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101".to_string());
+//! # assert_eq!(format!("{result:?}"), "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101".to_string());
 //! ```
 //! ```rust
+//! # use barcode_rs::BarcodeFormat;
 //! let my_data = "7501031311309".chars().collect::<Vec<char>>();
-//! let result: bit_vec::BitVec = barcode_rs::encode(&my_data, BarcodeFormat::EAN13).unwrap();
+//! let result = barcode_rs::encode(&my_data, BarcodeFormat::EAN13).unwrap();
+//! assert_eq!((result.width(), result.height()), (95, 1));
+//! let result = result.as_linear().unwrap();
 //!
-//! # assert_eq!(result, "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101".to_string());
+//! # assert_eq!(format!("{result:?}"), "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101".to_string());
 //! ```
 //!
 
-use std::{array::TryFromSliceError, char::TryFromCharError};
+use std::{array::TryFromSliceError, char::TryFromCharError, ops::Range};
 
 use bit_vec::BitVec;
+
 use format::{
-    Code11, DynamicBarcodeEncoding, Interleaved2Of5, Standard2Of5, StaticBarcodeEncoding, EAN13,
-    EAN8,
+    dyn_calc_checksum, AustraliaPost, AustraliaPostFormat, ChannelCode, Code11, Code16K, Code32,
+    Code49, Composite, CompositeLinear, DataBar, DataBarExpanded, DataBarLayout, DataBarLimited,
+    DotCode, EanAddOn, HanXin, HanXinEccLevel, Hibc, HibcSymbology, IntelligentMail,
+    Interleaved2Of5, JapanPost, Kix, Mailmark, MaxiCode, MaxiCodeMode, Pharmacode,
+    PharmacodeWidths, Planet, Postnet, Pzn, Rm4scc, Standard2Of5, Telepen, EAN13, EAN8,
 };
+use layout::composite_ranges;
 
+mod elements;
 mod encoding_source;
+mod layout;
+mod options;
+mod symbol;
+
+pub use elements::Elements;
+pub use encoding_source::{BorrowEncodingSource, OwnedEncodingSource};
+pub use layout::{Layout, QuietZone};
+pub use options::{Checksum, EncodeOptions};
+pub use symbol::{FourStateBar, Matrix, StackedRow, Symbol, TwoStateBar};
 
 /// Format implementations
 pub mod format;
+/// Renderers of encoded symbols
+pub mod render;
 
 /// Supported Barcode Formats
 #[non_exhaustive]
@@ -52,6 +112,62 @@ pub enum BarcodeFormat {
     Interleaved2Of5,
     /// Standard 2 of 5 format
     Standard2Of5,
+    /// MaxiCode format in the given mode
+    MaxiCode(MaxiCodeMode),
+    /// DotCode format
+    DotCode,
+    /// Han Xin Code format at the given error correction level
+    HanXin(HanXinEccLevel),
+    /// GS1 DataBar format with the given layout
+    DataBar(DataBarLayout),
+    /// GS1 DataBar Limited format
+    DataBarLimited,
+    /// GS1 DataBar Expanded format
+    DataBarExpanded,
+    /// GS1 DataBar Expanded Stacked format with the given number of segments per row
+    DataBarExpandedStacked(usize),
+    /// GS1 Composite format over the given linear component, the primary message and the 2D
+    /// component's element string separated by `|`
+    Composite(CompositeLinear),
+    /// USPS Intelligent Mail format, the 20 digit tracking code followed by the routing ZIP
+    /// code, a mailer ID starting with 9 having 9 digits
+    IntelligentMail,
+    /// USPS POSTNET format
+    Postnet,
+    /// USPS PLANET format
+    Planet,
+    /// Royal Mail 4-State Customer Code format
+    Rm4scc,
+    /// Dutch KIX format
+    Kix,
+    /// Royal Mail Mailmark 4-state format
+    Mailmark,
+    /// Australia Post 4-state format with the given barcode
+    AustraliaPost(AustraliaPostFormat),
+    /// Japan Post customer barcode format, the postal code followed by the address number
+    JapanPost,
+    /// Pharmacode one-track format with the standard bar widths
+    Pharmacode,
+    /// Pharmacode two-track format with the standard bar widths
+    PharmacodeTwoTrack,
+    /// German PZN8 format, the product number with or without its check digit
+    Pzn,
+    /// German PZN7 format, the product number with or without its check digit
+    Pzn7,
+    /// Italian Code 32 format, the AIC number with or without its check digit
+    Code32,
+    /// HIBC format in the given symbology, the data structure following the `+` flag character
+    Hibc(HibcSymbology),
+    /// Telepen format, full ASCII
+    Telepen,
+    /// Telepen Numeric format, digits with a lone last digit marked by a following `X`
+    TelepenNumeric,
+    /// Channel Code format, up to 7 digits in the fewest channels keeping leading zeros
+    ChannelCode,
+    /// Code 16K format, ASCII data
+    Code16K,
+    /// Code 49 format, ASCII data
+    Code49,
 }
 
 /// Encoding Error
@@ -63,6 +179,16 @@ pub enum EncodingError {
     WrongChar,
     /// The input data contains a checksum but its invalid
     WrongCheckusm,
+    /// The input data does not fit in the selected encoding
+    TooLong,
+    /// The selected check characters are not supported by the encoding
+    UnsupportedChecksum,
+    /// The wide to narrow ratio is outside of 2.0 to 3.0, or a width has a zero denominator
+    WrongWidths,
+    /// The element widths are not whole numbers of modules, only laid out by [`encode_layout`]
+    FractionalWidths,
+    /// Light modules cannot be added around MaxiCode or 4-state and 2-state bars
+    UnsupportedQuietZone,
 }
 
 impl From<TryFromSliceError> for EncodingError {
@@ -76,44 +202,287 @@ impl From<TryFromCharError> for EncodingError {
         Self::WrongChar
     }
 }
-/// Main encoding function, see [`BarcodeFormat`] for available formats
-pub fn encode(data: &[char], format: BarcodeFormat) -> Result<BitVec, EncodingError> {
-    match format {
-        BarcodeFormat::Code11 => Code11::encode(data),
-        BarcodeFormat::EAN13 => {
-            if data.len() == 12 {
-                EAN13::encode(TryInto::<&[_; 12]>::try_into(data)?)
-            } else if data.len() == 13 {
-                EAN13::encode(TryInto::<&[_; 13]>::try_into(data)?)
-            } else {
-                Err(EncodingError::WrongSize)?
+/// Main encoding function, see [`BarcodeFormat`] for available formats, encoding with the
+/// default [`EncodeOptions`]
+pub fn encode(data: &[char], format: BarcodeFormat) -> Result<Symbol, EncodingError> {
+    encode_with(data, format, &EncodeOptions::default())
+}
+
+/// Encoding function taking the check characters, element widths, add-on and quiet zone from
+/// the [`EncodeOptions`].
+///
+/// Element widths which are not whole numbers of modules give
+/// [`EncodingError::FractionalWidths`], [`encode_layout`] laying them out in fractions of a
+/// module.
+pub fn encode_with(
+    data: &[char],
+    format: BarcodeFormat,
+    options: &EncodeOptions,
+) -> Result<Symbol, EncodingError> {
+    let layout = encode_layout(data, format, options)?;
+    if layout.scale > 1 {
+        return Err(EncodingError::FractionalWidths);
+    }
+    Ok(layout.symbol)
+}
+
+/// Encoding function like [`encode_with`], also returning the quiet zone required by the
+/// symbology and the modules of the guard patterns and data characters, in fractions of a
+/// module for fractional element widths (see [`Layout::scale`])
+pub fn encode_layout(
+    data: &[char],
+    format: BarcodeFormat,
+    options: &EncodeOptions,
+) -> Result<Layout, EncodingError> {
+    let mut quiet_zone = format.quiet_zone();
+    if options.add_on.is_some() && matches!(format, BarcodeFormat::EAN13 | BarcodeFormat::EAN8) {
+        quiet_zone.right = EanAddOn::QUIET_ZONE;
+    }
+    let (symbol, scale, guards, characters) = match encode_elements(data, &format, options)? {
+        Some((elements, guards, characters)) => {
+            let units = elements.character_units();
+            let ranges = |indices: Vec<usize>| {
+                indices
+                    .into_iter()
+                    .map(|i| units[i].clone())
+                    .collect::<Vec<_>>()
+            };
+            (
+                elements.modules().into(),
+                elements.scale,
+                ranges(guards),
+                ranges(characters),
+            )
+        }
+        None => {
+            let linear = match format {
+                BarcodeFormat::Composite(linear) => Some(linear),
+                _ => None,
+            };
+            let symbol = encode_format(data, format)?;
+            let (guards, characters) = linear
+                .map(|linear| composite_ranges(linear, &symbol))
+                .unwrap_or_default();
+            (symbol, 1, guards, characters)
+        }
+    };
+
+    // quiet zones in modules, padded in units of the scale
+    let required = |width: usize| {
+        if options.include_quiet_zone {
+            scale * options.quiet_zone.max(width)
+        } else {
+            scale * options.quiet_zone
+        }
+    };
+    let (left, right) = (required(quiet_zone.left), required(quiet_zone.right));
+    let pad = |modules: &BitVec| {
+        let mut padded = BitVec::from_elem(left, false);
+        padded.extend(modules);
+        padded.grow(right, false);
+        padded
+    };
+    let symbol = match symbol {
+        symbol if left + right == 0 => symbol,
+        Symbol::Linear(modules) => Symbol::Linear(pad(&modules)),
+        Symbol::Stacked(rows) => Symbol::Stacked(
+            rows.into_iter()
+                .map(|row| StackedRow {
+                    modules: pad(&row.modules),
+                    height: row.height,
+                })
+                .collect(),
+        ),
+        // as many light rows above and below as columns left and right
+        Symbol::Matrix(matrix) => {
+            let (width, height) = (
+                left + matrix.width() + right,
+                left + matrix.height() + right,
+            );
+            let modules = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| x >= left && y >= left && matrix.get(x - left, y - left))
+                .collect();
+            Symbol::Matrix(Matrix::new(width, height, modules))
+        }
+        _ => return Err(EncodingError::UnsupportedQuietZone),
+    };
+    let shift = |ranges: Vec<Range<usize>>| {
+        ranges
+            .into_iter()
+            .map(|range| range.start + left..range.end + left)
+            .collect()
+    };
+    Ok(Layout {
+        symbol,
+        scale,
+        quiet_zone,
+        guards: shift(guards),
+        characters: shift(characters),
+    })
+}
+
+/// Elements of the formats encoded as bars and spaces of varying widths, with the indices of
+/// their guard pattern and data characters
+#[allow(clippy::type_complexity)]
+fn encode_elements(
+    data: &[char],
+    format: &BarcodeFormat,
+    options: &EncodeOptions,
+) -> Result<Option<(Elements, Vec<usize>, Vec<usize>)>, EncodingError> {
+    let (mut elements, guards) = match format {
+        BarcodeFormat::Code11 => {
+            let mut data = data.to_vec();
+            if matches!(options.checksum, Checksum::Single | Checksum::Double) {
+                data.push(Code11::c_check(&data));
             }
+            if options.checksum == Checksum::Double {
+                data.push(Code11::k_check(&data));
+            }
+            let widths = options.widths.unwrap_or(Code11::WIDTHS);
+            (Code11::elements(&data, widths)?, vec![])
+        }
+        BarcodeFormat::Interleaved2Of5 => {
+            let mut data = with_check_digit(data, options.checksum)?;
+            if data.len() % 2 == 1 {
+                match options.checksum {
+                    Checksum::None => Err(EncodingError::WrongSize)?,
+                    Checksum::Single => data.insert(0, '0'),
+                    _ => {}
+                }
+            }
+            let widths = options.widths.unwrap_or(Interleaved2Of5::WIDTHS);
+            (Interleaved2Of5::elements(&data, widths)?, vec![])
+        }
+        BarcodeFormat::Standard2Of5 => {
+            let data = with_check_digit(data, options.checksum)?;
+            let widths = options.widths.unwrap_or(Standard2Of5::WIDTHS);
+            (Standard2Of5::elements(&data, widths)?, vec![])
+        }
+        BarcodeFormat::EAN13 => (EAN13::elements(data)?, vec![0, 7, 14]),
+        BarcodeFormat::EAN8 => (EAN8::elements(data)?, vec![0, 5, 10]),
+        _ => return Ok(None),
+    };
+
+    // the start and stop characters and the guard patterns are not data characters
+    let mut characters = (1..elements.characters.len() - 1)
+        .filter(|i| !guards.contains(i))
+        .collect::<Vec<_>>();
+    if let Some(add_on) = &options.add_on {
+        if matches!(format, BarcodeFormat::EAN13 | BarcodeFormat::EAN8) {
+            let add_on = EanAddOn::elements(&add_on.chars().collect::<Vec<_>>())?;
+            // digits after the start pattern, every other character past the separators
+            let offset = elements.characters.len();
+            characters.extend((offset + 1..offset + add_on.characters.len()).step_by(2));
+            elements.elements.push((false, EanAddOn::GAP));
+            elements.append(add_on);
         }
-        BarcodeFormat::EAN8 => {
-            if data.len() == 7 {
-                EAN8::encode(TryInto::<&[_; 7]>::try_into(data)?)
-            } else if data.len() == 8 {
-                EAN8::encode(TryInto::<&[_; 8]>::try_into(data)?)
-            } else {
+    }
+    Ok(Some((elements, guards, characters)))
+}
+
+/// Digits followed by their modulo 10 check digit for [`Checksum::Single`]
+fn with_check_digit(data: &[char], checksum: Checksum) -> Result<Vec<char>, EncodingError> {
+    if !data.iter().all(char::is_ascii_digit) {
+        return Err(EncodingError::WrongChar);
+    }
+    if checksum == Checksum::Double {
+        return Err(EncodingError::UnsupportedChecksum);
+    }
+    let mut data = data.to_vec();
+    if checksum == Checksum::Single {
+        data.push(char::from(b'0' + dyn_calc_checksum(&data)));
+    }
+    Ok(data)
+}
+
+/// Symbol of the format with its own options, for the formats not encoded as elements by
+/// [`encode_elements`]
+fn encode_format(data: &[char], format: BarcodeFormat) -> Result<Symbol, EncodingError> {
+    Ok(match format {
+        BarcodeFormat::MaxiCode(mode) => MaxiCode::encode(&mode, data)?.into(),
+        BarcodeFormat::DotCode => DotCode::encode(data)?.into(),
+        BarcodeFormat::HanXin(ecc_level) => HanXin::encode(ecc_level, data)?.into(),
+        BarcodeFormat::DataBar(layout) => DataBar::encode(layout, data)?,
+        BarcodeFormat::DataBarLimited => DataBarLimited::encode(data)?,
+        BarcodeFormat::DataBarExpanded => DataBarExpanded::encode(data)?,
+        BarcodeFormat::DataBarExpandedStacked(segments) => {
+            DataBarExpanded::encode_stacked(segments, data)?
+        }
+        BarcodeFormat::Composite(linear) => {
+            let split = data
+                .iter()
+                .position(|&c| c == '|')
+                .ok_or(EncodingError::WrongChar)?;
+            Composite::encode(linear, &data[..split], &data[split + 1..])?
+        }
+        BarcodeFormat::IntelligentMail => {
+            if data.len() < 20 {
                 Err(EncodingError::WrongSize)?
             }
+            let mailer_end = if data[5] == '9' { 14 } else { 11 };
+            IntelligentMail::encode(
+                &data[..2],
+                &data[2..5],
+                &data[5..mailer_end],
+                &data[mailer_end..20],
+                &data[20..],
+            )?
+        }
+        BarcodeFormat::Postnet => Postnet::encode(data)?,
+        BarcodeFormat::Planet => Planet::encode(data)?,
+        BarcodeFormat::Rm4scc => Rm4scc::encode(data)?,
+        BarcodeFormat::Kix => Kix::encode(data)?,
+        BarcodeFormat::Mailmark => Mailmark::encode(data)?,
+        BarcodeFormat::AustraliaPost(format) => AustraliaPost::encode(format, data)?,
+        BarcodeFormat::JapanPost => JapanPost::encode(data)?,
+        BarcodeFormat::Pharmacode => {
+            Pharmacode::encode(PharmacodeWidths::ONE_TRACK, parse_integer(data)?)?
         }
-        BarcodeFormat::Interleaved2Of5 => Interleaved2Of5::encode(data),
-        BarcodeFormat::Standard2Of5 => Standard2Of5::encode(data),
+        BarcodeFormat::PharmacodeTwoTrack => {
+            Pharmacode::encode_two_track(PharmacodeWidths::TWO_TRACK, parse_integer(data)?)?
+        }
+        BarcodeFormat::Pzn => Pzn::encode(data)?,
+        BarcodeFormat::Pzn7 => Pzn::encode_pzn7(data)?,
+        BarcodeFormat::Code32 => Code32::encode(data)?,
+        BarcodeFormat::Hibc(symbology) => Hibc::encode(symbology, data)?,
+        BarcodeFormat::Telepen => Telepen::encode(data)?,
+        BarcodeFormat::TelepenNumeric => Telepen::encode_numeric(data)?,
+        BarcodeFormat::ChannelCode => ChannelCode::encode(data)?,
+        BarcodeFormat::Code16K => Code16K::encode(data)?,
+        BarcodeFormat::Code49 => Code49::encode(data)?,
+        BarcodeFormat::Code11
+        | BarcodeFormat::EAN13
+        | BarcodeFormat::EAN8
+        | BarcodeFormat::Interleaved2Of5
+        | BarcodeFormat::Standard2Of5 => unreachable!("encoded from their elements"),
+    })
+}
+
+/// Value of a decimal integer of at most 9 digits
+fn parse_integer(data: &[char]) -> Result<u32, EncodingError> {
+    if data.len() > 9 {
+        return Err(EncodingError::TooLong);
     }
+    data.iter()
+        .try_fold(0, |acc, c| Some(acc * 10 + c.to_digit(10)?))
+        .ok_or(EncodingError::WrongChar)
 }
 
 #[cfg(test)]
 mod tests {
     use std::assert_eq;
 
+    use bit_vec::BitVec;
+
     use crate::encoding_source::BorrowEncodingSource;
 
     use super::*;
     #[test]
     fn code_11() {
         let result = "123-4530".barcode_encode(BarcodeFormat::Code11).unwrap();
-        let result = result
+        let result = BitVec::try_from(result)
+            .unwrap()
             .into_iter()
             .map(|a| if a { "1" } else { "0" })
             .collect::<String>();
@@ -128,7 +497,8 @@ mod tests {
         let result = "7501031311309"
             .barcode_encode(BarcodeFormat::EAN13)
             .unwrap();
-        let result = result
+        let result = BitVec::try_from(result)
+            .unwrap()
             .into_iter()
             .map(|a| if a { "1" } else { "0" })
             .collect::<String>();
@@ -138,7 +508,8 @@ mod tests {
     #[test]
     fn ean_8() {
         let result = "55123457".barcode_encode(BarcodeFormat::EAN8).unwrap();
-        let result = result
+        let result = BitVec::try_from(result)
+            .unwrap()
             .into_iter()
             .map(|a| if a { "1" } else { "0" })
             .collect::<String>();
@@ -157,7 +528,8 @@ mod tests {
             .barcode_encode(BarcodeFormat::Interleaved2Of5)
             .unwrap();
         assert_eq!(result, result2);
-        let result = result
+        let result = BitVec::try_from(result)
+            .unwrap()
             .into_iter()
             .map(|a| if a { "1" } else { "0" })
             .collect::<String>();
@@ -172,11 +544,1252 @@ mod tests {
             .barcode_encode(BarcodeFormat::Standard2Of5)
             .unwrap();
 
-        let result = result
+        let result = BitVec::try_from(result)
+            .unwrap()
             .into_iter()
             .map(|a| if a { "1" } else { "0" })
             .collect::<String>();
 
         assert_eq!(&result, "1101101011101010101110101110101011101110111010101010101110101110111010111010101011101110101010101011101110101011101110101101011");
     }
+
+    #[test]
+    fn maxicode() {
+        use crate::format::{CarrierMessage, MaxiCode, MaxiCodeMode, MaxiCodeSymbol};
+
+        let mode = MaxiCodeMode::Structured(CarrierMessage {
+            postal_code: "152382802".to_string(),
+            country: 840,
+            service_class: 1,
+        });
+        let data = "[)>\u{1e}01\u{1d}961Z00004951\u{1d}UPSN\u{1d}06X610\u{1d}159\u{1d}1234567\u{1d}1/1\u{1d}\u{1d}Y\u{1d}634 ALPHA DR\u{1d}PITTSBURGH\u{1d}PA\u{1e}\u{4}"
+            .chars()
+            .collect::<Vec<_>>();
+        let result = MaxiCode::encode(&mode, &data).unwrap();
+
+        // orientation patterns around the bullseye
+        for (x, y) in [
+            (10, 9),
+            (11, 9),
+            (11, 10),
+            (7, 15),
+            (8, 16),
+            (20, 16),
+            (20, 17),
+        ] {
+            assert!(result.get(x, y));
+        }
+        for (x, y) in [(17, 9), (17, 10), (18, 10), (7, 16), (21, 16)] {
+            assert!(!result.get(x, y));
+        }
+        // mode 2 in the low nibble of the first codeword
+        assert!(
+            !result.get(16, 9) && !result.get(16, 10) && result.get(17, 11) && !result.get(16, 11)
+        );
+        assert_eq!(
+            result.bullseye().center,
+            MaxiCodeSymbol::module_center(14, 16)
+        );
+        assert_eq!(
+            result.coordinates().len(),
+            (0..MaxiCodeSymbol::ROWS)
+                .flat_map(|y| (0..MaxiCodeSymbol::COLUMNS).map(move |x| (x, y)))
+                .filter(|&(x, y)| result.get(x, y))
+                .count()
+        );
+
+        // reference symbols from zint
+        let rows = |symbol: &MaxiCodeSymbol| {
+            (0..MaxiCodeSymbol::ROWS)
+                .map(|y| {
+                    (0..MaxiCodeSymbol::COLUMNS)
+                        .map(|x| if symbol.get(x, y) { '1' } else { '0' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rows(&result),
+            [
+                "110101110110111110111111101111",
+                "010101010111000011011000010010",
+                "110110110001001010100110010001",
+                "111000101010101111111111111110",
+                "001111000010110010011000000011",
+                "001001110010101010100000000000",
+                "111011111110111111101111111110",
+                "100110000011001001110000001010",
+                "010001100010101010101001110001",
+                "110111100011010000011011111100",
+                "001100110011110000001110101001",
+                "101110101000000001011111011010",
+                "101010000000000000010110111111",
+                "111101100000000000011011100010",
+                "101010010000000000000110011101",
+                "001000010000000000011100011110",
+                "010011001000000000001000001010",
+                "000000101000000000001010000010",
+                "000100111100000000001110101010",
+                "000010101100000000001000110000",
+                "100000111010000000011101100000",
+                "101000100000000000110110100000",
+                "001000001110100101100110100101",
+                "011001110010101001100000001000",
+                "000010100010110001010101111010",
+                "100111000011111000001101101010",
+                "110010001001010010100000001101",
+                "000001000110110100111010111100",
+                "010111010010100100001111011010",
+                "110111001110101010101101110100",
+                "011011110001010100010111010011",
+                "000111101001100001111000010110",
+                "000100101000110000000111110011",
+            ]
+        );
+        let data = "MaxiCode (19 chars)".chars().collect::<Vec<_>>();
+        let result = MaxiCode::encode(&MaxiCodeMode::Standard, &data).unwrap();
+        assert_eq!(
+            rows(&result),
+            [
+                "000111011111010000001010110111",
+                "101101010001110001000000110110",
+                "101110001010111100100111111011",
+                "010101010101010101010101010100",
+                "000000000000000000000000000011",
+                "101010101010101010101010101010",
+                "010101010101010101010101010111",
+                "000000000000000000000000000010",
+                "101010101010101010101010101011",
+                "010101011111111100000001010100",
+                "000000000011110110001000000001",
+                "101010101110000000111010101010",
+                "010101100010000000001101010110",
+                "000000101000000000001000000010",
+                "101010000000000000011010101001",
+                "010101010000000000001101010110",
+                "000000001000000000001000000010",
+                "101010110000000000001010101010",
+                "010101101100000000010101010111",
+                "000000100000000000000000000000",
+                "101010010110000000000110101011",
+                "010101010110000000001001010110",
+                "000000000110001011000000000011",
+                "101010100110111001010010101010",
+                "010101010101010101011100100010",
+                "000000000000000000000000101100",
+                "101010101010101010101010101101",
+                "011000111100100001011011100100",
+                "001010011100001010001111100100",
+                "111010000011101011110001010100",
+                "000111001100100000010011000110",
+                "100000101101100000001000111110",
+                "011101000100000001001111101001",
+            ]
+        );
+
+        let too_long = ['a'; 200];
+        assert!(matches!(
+            MaxiCode::encode(&MaxiCodeMode::Standard, &too_long),
+            Err(EncodingError::TooLong)
+        ));
+    }
+
+    #[test]
+    fn dotcode() {
+        use crate::format::{DotCode, DotCodeSymbol};
+
+        let data = "0100000000000000\u{1d}21ABC123".chars().collect::<Vec<_>>();
+        let result = DotCode::encode(&data).unwrap();
+        assert_eq!((result.width() + result.height()) % 2, 1);
+        for y in 0..result.height() {
+            for x in 0..result.width() {
+                if result.get(x, y) {
+                    assert!(DotCodeSymbol::is_dot_position(x, y));
+                }
+            }
+        }
+
+        let result = DotCode::encode(&['1', '2', '3', '4', '5', '6', '7', '8']).unwrap();
+        assert_eq!((result.width(), result.height()), (17, 12));
+        let top = (0..17)
+            .map(|x| if result.get(x, 0) { '1' } else { '0' })
+            .collect::<String>();
+        assert_eq!(top, "10001010100010101");
+        // the dots of the next row are not read past the end of a row
+        let dark = (0..17).find(|&x| result.get(x, 1)).unwrap();
+        assert!(!result.get(17 + dark, 0) && !result.get(0, 12));
+
+        assert!(DotCode::encode(&['é']).is_ok());
+        assert!(matches!(
+            DotCode::encode(&['€']),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn hanxin() {
+        use crate::format::{HanXin, HanXinEccLevel};
+
+        let data = "汉信码 12345".chars().collect::<Vec<_>>();
+        let result = HanXin::encode(HanXinEccLevel::L2, &data).unwrap();
+        assert_eq!((result.version(), result.size()), (1, 23));
+        let row = |y| {
+            (0..result.size())
+                .map(|x| if result.get(x, y) { '1' } else { '0' })
+                .collect::<String>()
+        };
+        assert_eq!(row(0), "11111110001100101111111");
+        assert_eq!(row(1), "10000000010110100000001");
+        assert_eq!(row(8), "00010101001010100000000");
+        // the modules of the next row are not read past the end of a row
+        assert!(result.get(0, 1) && !result.get(23, 0) && !result.get(0, 23));
+
+        let result = HanXin::encode_version(10, HanXinEccLevel::L4, &['a']).unwrap();
+        assert_eq!(result.size(), 41);
+        assert!(matches!(
+            HanXin::encode_version(1, HanXinEccLevel::L4, &['a'; 30]),
+            Err(EncodingError::TooLong)
+        ));
+    }
+
+    #[test]
+    fn databar() {
+        use crate::format::DataBarLayout;
+
+        let row = |symbol: &Symbol, y| {
+            (0..symbol.width())
+                .map(|x| if symbol.get(x, y) { '1' } else { '0' })
+                .collect::<String>()
+        };
+
+        let omni = "1234567890123"
+            .barcode_encode(BarcodeFormat::DataBar(DataBarLayout::Omnidirectional))
+            .unwrap();
+        assert_eq!(
+            row(&omni, 0),
+            "010111010010000001001110000000010100001011111010110100011001100101111111110001011011000111000101"
+        );
+        let with_check = "12345678901231"
+            .barcode_encode(BarcodeFormat::DataBar(DataBarLayout::Truncated))
+            .unwrap();
+        assert_eq!(omni.as_linear(), with_check.as_linear());
+        assert!(matches!(
+            "12345678901232".barcode_encode(BarcodeFormat::DataBar(DataBarLayout::Truncated)),
+            Err(EncodingError::WrongCheckusm)
+        ));
+
+        let stacked = "1234567890123"
+            .barcode_encode(BarcodeFormat::DataBar(DataBarLayout::Stacked))
+            .unwrap();
+        assert_eq!((stacked.width(), stacked.height()), (50, 3));
+        assert_eq!(
+            row(&stacked, 1),
+            "00001010100110111010000101011110101101010000110000"
+        );
+        let stacked = "1234567890123"
+            .barcode_encode(BarcodeFormat::DataBar(
+                DataBarLayout::StackedOmnidirectional,
+            ))
+            .unwrap();
+        assert_eq!(
+            row(&stacked, 3),
+            "00001011100110011010000000001010100100111000110000"
+        );
+
+        let limited = "0950110153000"
+            .barcode_encode(BarcodeFormat::DataBarLimited)
+            .unwrap();
+        assert_eq!(
+            row(&limited, 0),
+            "0101100001100111101101010001001010101110100101001000101101100100111001110100000"
+        );
+        assert!("2950110153000"
+            .barcode_encode(BarcodeFormat::DataBarLimited)
+            .is_err());
+
+        let expanded = "(01)98898765432106(3202)012345"
+            .barcode_encode(BarcodeFormat::DataBarExpanded)
+            .unwrap();
+        assert_eq!(
+            row(&expanded, 0),
+            "01001110001000010110111111110000101011000100001110011010000001100010101111110000111010011100000010010100111110111001100011111100001011101100000100100100011110010110001011111111001110011001111010000101"
+        );
+        let stacked = "[01]98898765432106[3202]012345"
+            .barcode_encode(BarcodeFormat::DataBarExpandedStacked(4))
+            .unwrap();
+        assert_eq!((stacked.width(), stacked.height()), (102, 5));
+        assert_eq!(
+            row(&stacked, 2),
+            "000001010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010000"
+        );
+    }
+
+    #[test]
+    fn composite() {
+        use crate::format::CompositeComponent;
+
+        let row = |symbol: &Symbol, y| {
+            (0..symbol.width())
+                .map(|x| if symbol.get(x, y) { '1' } else { '0' })
+                .collect::<String>()
+        };
+
+        let ean = "331234567890|(99)1234-abcd"
+            .barcode_encode(BarcodeFormat::Composite(CompositeLinear::EAN13))
+            .unwrap();
+        assert_eq!((ean.width(), ean.height()), (99, 7));
+        assert_eq!(
+            row(&ean, 0),
+            "110110111011100110111011110100010100000010001001110100111011010110000001100110010000100011011000101"
+        );
+
+        let primary = "(01)03312345678903".chars().collect::<Vec<_>>();
+        let composite = "(99)1234-abcd".chars().collect::<Vec<_>>();
+        let cc_c = Composite::encode_component(
+            CompositeLinear::GS1128,
+            CompositeComponent::CcC,
+            &primary,
+            &composite,
+        )
+        .unwrap();
+        assert!(row(&cc_c, 0).contains("11111111010101000"));
+        assert!(matches!(
+            Composite::encode_component(
+                CompositeLinear::EAN8,
+                CompositeComponent::CcC,
+                &['1'; 7],
+                &composite,
+            ),
+            Err(EncodingError::TooLong)
+        ));
+        assert!(matches!(
+            "331234567890".barcode_encode(BarcodeFormat::Composite(CompositeLinear::EAN13)),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    fn four_state(symbol: Symbol) -> String {
+        let Symbol::FourState(bars) = symbol else {
+            panic!("not a 4-state symbol")
+        };
+        bars.iter()
+            .map(|bar| match bar {
+                FourStateBar::Full => 'F',
+                FourStateBar::Ascender => 'A',
+                FourStateBar::Descender => 'D',
+                FourStateBar::Tracker => 'T',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn intelligent_mail() {
+        let result = "0123456709498765432101234567891"
+            .barcode_encode(BarcodeFormat::IntelligentMail)
+            .unwrap();
+        assert_eq!(
+            four_state(result),
+            "AADTFFDFTDADTAADAATFDTDDAAADDTDTTDAFADADDDTFFFDDTTTADFAAADFTDAADA"
+        );
+
+        let tracking = |s: &str| s.chars().collect::<Vec<_>>();
+        assert!(matches!(
+            IntelligentMail::encode(
+                &tracking("05"),
+                &tracking("234"),
+                &tracking("567094"),
+                &tracking("987654321"),
+                &[],
+            ),
+            Err(EncodingError::WrongChar)
+        ));
+        assert!(matches!(
+            IntelligentMail::encode(
+                &tracking("01"),
+                &tracking("234"),
+                &tracking("567094"),
+                &tracking("987654321"),
+                &tracking("0123"),
+            ),
+            Err(EncodingError::WrongSize)
+        ));
+    }
+
+    #[test]
+    fn postnet() {
+        let heights = |symbol: Symbol| match symbol {
+            Symbol::TwoState(bars) => bars
+                .iter()
+                .map(|&bar| if bar == TwoStateBar::Tall { '|' } else { '.' })
+                .collect::<String>(),
+            _ => panic!("not a 2-state symbol"),
+        };
+        let result = "12345".barcode_encode(BarcodeFormat::Postnet).unwrap();
+        assert_eq!(heights(result), "|...||..|.|..||..|..|.|.|..|.|.|");
+        let result = "12345678901".barcode_encode(BarcodeFormat::Planet).unwrap();
+        assert_eq!((result.width(), result.height()), (123, 2));
+        assert!(matches!(
+            "1234".barcode_encode(BarcodeFormat::Postnet),
+            Err(EncodingError::WrongSize)
+        ));
+        assert!(matches!(
+            "1234a".barcode_encode(BarcodeFormat::Postnet),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn royal_mail() {
+        let result = "SN34RD1A".barcode_encode(BarcodeFormat::Rm4scc).unwrap();
+        assert_eq!(four_state(result), "AFTFTFDTADTAFDTFAFTADTFADTDAFDADAADDAF");
+        let result = "2500GG30250".barcode_encode(BarcodeFormat::Kix).unwrap();
+        assert_eq!(
+            four_state(result),
+            "TDFADDAATTFFTTFFDAFTDAFTDTAFTTFFTDFADDAATTFF"
+        );
+        assert!(matches!(
+            "SN34 RD1A".barcode_encode(BarcodeFormat::Rm4scc),
+            Err(EncodingError::WrongChar)
+        ));
+
+        let result = "11210012341234567AB19XY1A"
+            .barcode_encode(BarcodeFormat::Mailmark)
+            .unwrap();
+        assert_eq!(
+            four_state(result),
+            "DTTDTFTDDTATFTATDATAFDTATFTFTADTFDTTFTADFDDFDADFAAFDTFAADDDDTFDDFAAATDFDTTDTFA"
+        );
+        let result = "4103842241656XY11"
+            .barcode_encode(BarcodeFormat::Mailmark)
+            .unwrap();
+        assert_eq!(result.width(), 131);
+        assert!(matches!(
+            "4103842241656CI1A".barcode_encode(BarcodeFormat::Mailmark),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn australia_post() {
+        use crate::format::AustraliaPostFormat;
+
+        let result = "39987520"
+            .barcode_encode(BarcodeFormat::AustraliaPost(
+                AustraliaPostFormat::StandardCustomer,
+            ))
+            .unwrap();
+        assert_eq!(four_state(result), "ATFAFAAFTFTFDDDAADFDFFTTFDADATAFTFDAT");
+        let result = "3221132412345678"
+            .barcode_encode(BarcodeFormat::AustraliaPost(AustraliaPostFormat::Customer2))
+            .unwrap();
+        assert_eq!(
+            four_state(result),
+            "ATADTFAFFDFDFAFAAFFDAAFAFDAFAAADDFDADDAADTDDDFTDTTAT"
+        );
+        let result = "12345"
+            .barcode_encode(BarcodeFormat::AustraliaPost(AustraliaPostFormat::Routing))
+            .unwrap();
+        assert_eq!(four_state(result), "ATDDDAFFFFFFFAFDAFAAADTFATDAAADTAADAT");
+
+        // customer information shorter than the field, padded with filler bars
+        let short = "39987520AB"
+            .barcode_encode(BarcodeFormat::AustraliaPost(AustraliaPostFormat::Customer2))
+            .unwrap();
+        let full = "39987520AB123"
+            .barcode_encode(BarcodeFormat::AustraliaPost(AustraliaPostFormat::Customer2))
+            .unwrap();
+        let (short, full) = (four_state(short), four_state(full));
+        assert_eq!(short.len(), 52);
+        assert_eq!(short[22..28], full[22..28]);
+        assert_eq!(short[28..38], *"T".repeat(10));
+
+        assert!(matches!(
+            "322113241234567890123456"
+                .barcode_encode(BarcodeFormat::AustraliaPost(AustraliaPostFormat::Customer3)),
+            Err(EncodingError::WrongSize)
+        ));
+        assert!(matches!(
+            "32211324A2345678"
+                .barcode_encode(BarcodeFormat::AustraliaPost(AustraliaPostFormat::Customer2)),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn japan_post() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        let data = JapanPost::address_number(
+            &chars("154-0023"),
+            &chars("3丁目16−4 ＡＢＣマンション205号室"),
+        )
+        .unwrap();
+        assert_eq!(data, chars("15400233-16-4-205"));
+        let data =
+            JapanPost::address_number(&chars("1540023"), &chars("三丁目十六番地四号")).unwrap();
+        assert_eq!(data, chars("15400233-16-4"));
+
+        let result = JapanPost::encode(&chars("15400233-16-4-205")).unwrap();
+        assert_eq!(
+            four_state(result),
+            "FDFFTFTFFADFTTFTTFDADFADFATFTFFTDAFTFTFADTFTFDAFTTFTFTDATDATDADAFDF"
+        );
+        assert!(matches!(
+            "1540023#".barcode_encode(BarcodeFormat::JapanPost),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn pharmacode() {
+        let result = "1234".barcode_encode(BarcodeFormat::Pharmacode).unwrap();
+        let modules = result.as_linear().unwrap();
+        assert_eq!(
+            format!("{modules:?}"),
+            "10010011100111001001110010010011100111"
+        );
+        assert_eq!(
+            Pharmacode::decode(PharmacodeWidths::ONE_TRACK, modules),
+            Some(1234)
+        );
+
+        let widths = PharmacodeWidths {
+            narrow: 2,
+            wide: 6,
+            space: 3,
+        };
+        let result = Pharmacode::encode(widths, 131070).unwrap();
+        assert_eq!(result.width(), 16 * 6 + 15 * 3);
+        assert_eq!(
+            Pharmacode::decode(widths, result.as_linear().unwrap()),
+            Some(131070)
+        );
+
+        let result = "64570080"
+            .barcode_encode(BarcodeFormat::PharmacodeTwoTrack)
+            .unwrap();
+        assert_eq!((result.width(), result.height()), (31, 2));
+        assert_eq!(Pharmacode::decode_two_track(&result), Some(64570080));
+
+        assert!(matches!(
+            "2".barcode_encode(BarcodeFormat::Pharmacode),
+            Err(EncodingError::WrongSize)
+        ));
+        assert!(matches!(
+            "131071".barcode_encode(BarcodeFormat::Pharmacode),
+            Err(EncodingError::TooLong)
+        ));
+    }
+
+    #[test]
+    fn pharmacy() {
+        let result = "1234567".barcode_encode(BarcodeFormat::Pzn).unwrap();
+        assert_eq!(
+            format!("{:?}", result.as_linear().unwrap()),
+            "1000101110111010100010101110111011101000101011101011100010101110111011100010101010100011101011101110100011101010101110001110101010100010111011101110100010111010100010111011101"
+        );
+        assert!("12345678".barcode_encode(BarcodeFormat::Pzn).is_ok());
+        assert!(matches!(
+            "12345670".barcode_encode(BarcodeFormat::Pzn),
+            Err(EncodingError::WrongCheckusm)
+        ));
+
+        let result = "123456".barcode_encode(BarcodeFormat::Pzn7).unwrap();
+        assert_eq!(result.width(), 159);
+
+        let result = "12345678".barcode_encode(BarcodeFormat::Code32).unwrap();
+        assert_eq!(
+            format!("{:?}", result.as_linear().unwrap()),
+            "1000101110111010111011100010101010111011101000101110101011100010111011101010001011101000101110101010111010001110100010111011101"
+        );
+        assert!("123456788".barcode_encode(BarcodeFormat::Code32).is_ok());
+        assert!(matches!(
+            "123456780".barcode_encode(BarcodeFormat::Code32),
+            Err(EncodingError::WrongCheckusm)
+        ));
+    }
+
+    #[test]
+    fn hibc() {
+        let primary = "A123BJC5D6E71".chars().collect::<Vec<_>>();
+        assert_eq!(Hibc::check_character(&primary).unwrap(), 'G');
+        let result = Hibc::encode(HibcSymbology::Code39, &primary).unwrap();
+        assert_eq!(
+            format!("{:?}", result.as_linear().unwrap()),
+            "1000101110111010100010100010001011101010001011101110100010101110101110001010111011101110001010101011101000101110101011100011101011101110100010101110100011101010101011100010111010111000111010101110101110001010101000101110111011101000101011101010100011101110100010111011101"
+        );
+
+        let result = "A123BJC5D6E71/$$52001510X3"
+            .barcode_encode(BarcodeFormat::Hibc(HibcSymbology::Code128))
+            .unwrap();
+        assert_eq!(result.width(), 321);
+        assert!("$$3051231LOT12G"
+            .barcode_encode(BarcodeFormat::Hibc(HibcSymbology::Code128))
+            .is_ok());
+
+        assert!(matches!(
+            "1123BJC5D6E7A".barcode_encode(BarcodeFormat::Hibc(HibcSymbology::Code39)),
+            Err(EncodingError::WrongChar)
+        ));
+        assert!(matches!(
+            "A123BJC5D6E71/$$3051".barcode_encode(BarcodeFormat::Hibc(HibcSymbology::Code39)),
+            Err(EncodingError::WrongSize)
+        ));
+    }
+
+    #[test]
+    fn telepen() {
+        let result = "ABC".barcode_encode(BarcodeFormat::Telepen).unwrap();
+        assert_eq!(
+            format!("{:?}", result.as_linear().unwrap()),
+            "101010101011100010111011101110001110001110111000101011101110101011101000101000101110001010101010"
+        );
+        assert!(matches!(
+            "é".barcode_encode(BarcodeFormat::Telepen),
+            Err(EncodingError::WrongChar)
+        ));
+
+        let result = "1X".barcode_encode(BarcodeFormat::TelepenNumeric).unwrap();
+        assert_eq!(result.width(), 64);
+        assert!("12345"
+            .barcode_encode(BarcodeFormat::TelepenNumeric)
+            .is_ok());
+        assert!(matches!(
+            "X1".barcode_encode(BarcodeFormat::TelepenNumeric),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn channel_code() {
+        let result = "12345".barcode_encode(BarcodeFormat::ChannelCode).unwrap();
+        assert_eq!(
+            format!("{:?}", result.as_linear().unwrap()),
+            "1010101010111000010010110010111"
+        );
+        assert!(matches!(
+            "12345678".barcode_encode(BarcodeFormat::ChannelCode),
+            Err(EncodingError::TooLong)
+        ));
+    }
+
+    #[test]
+    fn stacked() {
+        let row = |symbol: &Symbol, y| {
+            (0..symbol.width())
+                .map(|x| if symbol.get(x, y) { '1' } else { '0' })
+                .collect::<String>()
+        };
+
+        let code_16k = "ABC123".barcode_encode(BarcodeFormat::Code16K).unwrap();
+        assert_eq!((code_16k.width(), code_16k.height()), (70, 5));
+        assert_eq!(
+            row(&code_16k, 1),
+            "1110010100110010011010111001110111010011101110111001011000110010001101"
+        );
+        assert_eq!(
+            row(&code_16k, 3),
+            "1100110100110001101001101000110010111101101100111011000101100110011001"
+        );
+
+        let code_49 = "ABC123".barcode_encode(BarcodeFormat::Code49).unwrap();
+        assert_eq!(
+            row(&code_49, 3),
+            "1011001111001011101001111001101000110110101111100010000001001011101111"
+        );
+        assert!(matches!(
+            "é".barcode_encode(BarcodeFormat::Code49),
+            Err(EncodingError::WrongChar)
+        ));
+    }
+
+    #[test]
+    fn terminal() {
+        use crate::render::TerminalRenderer;
+
+        let linear = Symbol::Linear(BitVec::from_bytes(&[0b1011_0010]));
+        let renderer = TerminalRenderer {
+            quiet_zone: 1,
+            bar_height: 1,
+            text: Some("AB".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            renderer.render(&linear),
+            "          \n █ ██  █  \n          \n    AB"
+        );
+
+        let matrix = Symbol::Matrix(Matrix::new(
+            2,
+            3,
+            vec![true, false, true, true, false, true],
+        ));
+        let renderer = TerminalRenderer {
+            quiet_zone: 0,
+            ..Default::default()
+        };
+        assert_eq!(renderer.render(&matrix), "█▄\n ▀");
+        let inverted = TerminalRenderer {
+            invert: true,
+            ..renderer.clone()
+        };
+        assert_eq!(inverted.render(&matrix), " ▀\n█▄");
+
+        // two pixels to a module width, the centre of the bullseye light and its outer ring dark
+        let maxicode = "ABC"
+            .barcode_encode(BarcodeFormat::MaxiCode(format::MaxiCodeMode::Standard))
+            .unwrap();
+        let lines = renderer
+            .render(&maxicode)
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!((lines[0].len(), lines.len()), (61, 29));
+        assert!(matches!(lines[14][29], ' ' | '▄'));
+        assert!(matches!(lines[14][20], '█' | '▀'));
+    }
+
+    #[test]
+    fn vector() {
+        use crate::render::{BarWidthReduction, VectorRenderer};
+
+        let linear = Symbol::Linear(BitVec::from_bytes(&[0b1011_0010]));
+        let renderer = VectorRenderer {
+            module_width: 2.0,
+            bar_height: 20.0,
+            quiet_zone: 1,
+            bar_width_reduction: BarWidthReduction::Micrometres(176.3888),
+            ..Default::default()
+        };
+        let eps = renderer.render_eps(&linear);
+        assert!(eps.contains("%%BoundingBox: 0 0 20 24\n"));
+        let bars = eps
+            .lines()
+            .filter(|line| line.ends_with("rectfill"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bars,
+            [
+                "2.25 2 1.5 20 rectfill",
+                "6.25 2 3.5 20 rectfill",
+                "14.25 2 1.5 20 rectfill"
+            ]
+        );
+
+        let renderer = VectorRenderer {
+            text: Some("(1)".to_string()),
+            ..renderer
+        };
+        let pdf = String::from_utf8(renderer.render_pdf(&linear)).unwrap();
+        assert!(pdf.contains("/MediaBox [0 0 20 36.5]"));
+        assert!(pdf.contains("(\\(1\\)) Tj"));
+        let xref = pdf.rsplit("startxref\n").next().unwrap();
+        let xref = xref.lines().next().unwrap().parse::<usize>().unwrap();
+        for (i, entry) in pdf[xref..].lines().skip(3).take(5).enumerate() {
+            let offset = entry[..10].parse::<usize>().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+
+        // hexagons of the dark modules and three dark rings, the centre of the bullseye 14.5
+        // modules right of the quiet zone
+        let maxicode = "ABC"
+            .barcode_encode(BarcodeFormat::MaxiCode(format::MaxiCodeMode::Standard))
+            .unwrap();
+        let Symbol::Hexagonal(hexagonal) = &maxicode else {
+            unreachable!()
+        };
+        let renderer = VectorRenderer {
+            text: None,
+            ..renderer
+        };
+        let eps = renderer.render_eps(&maxicode);
+        let hexagons = eps.lines().filter(|line| line.ends_with("closepath fill"));
+        assert_eq!(hexagons.count(), hexagonal.coordinates().len());
+        let rings = eps.lines().filter(|line| line.ends_with("eofill"));
+        assert_eq!(rings.count(), 3);
+        assert!(eps.contains(" 31 30.868 9.142 0 360 arc "));
+        let pdf = String::from_utf8(renderer.render_pdf(&maxicode)).unwrap();
+        assert_eq!(pdf.matches(" h f\n").count(), hexagonal.coordinates().len());
+        assert_eq!(pdf.matches("f*").count(), 3);
+    }
+
+    #[test]
+    fn bar_width_reduction() {
+        use crate::render::{BarWidthReduction, EscPosRenderer, LabelRenderer, VectorRenderer};
+
+        // bars of 1, 2 and 1 modules of 4 dots or points, narrowed by 2 around their centre
+        let linear = Symbol::Linear(BitVec::from_bytes(&[0b1011_0010]));
+        for (reduction, points) in [
+            (BarWidthReduction::Dots(2), BarWidthReduction::Dots(2)),
+            (
+                BarWidthReduction::Micrometres(200.0),
+                BarWidthReduction::Micrometres(705.5556),
+            ),
+        ] {
+            let vector = VectorRenderer {
+                module_width: 4.0,
+                bar_height: 10.0,
+                quiet_zone: 0,
+                bar_width_reduction: points,
+                ..Default::default()
+            };
+            let eps = vector.render_eps(&linear);
+            let bars = eps
+                .lines()
+                .filter(|line| line.ends_with("rectfill"))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                bars,
+                [
+                    "1 0 2 10 rectfill",
+                    "9 0 6 10 rectfill",
+                    "25 0 2 10 rectfill"
+                ]
+            );
+
+            // 100 micrometres to the dot at 254 dpi
+            let label = LabelRenderer {
+                dpi: 254,
+                module_width: 0.4,
+                bar_height: 1.0,
+                bar_width_reduction: reduction,
+                ..Default::default()
+            };
+            assert_eq!(
+                label.render(&linear),
+                "^XA\n^FO1,0^GB2,10,2^FS\n^FO9,0^GB6,10,6^FS\n^FO25,0^GB2,10,2^FS\n^XZ\n"
+            );
+
+            let escpos = EscPosRenderer {
+                module_width: 4,
+                height: 1,
+                dpi: 254,
+                bar_width_reduction: reduction,
+                ..Default::default()
+            };
+            assert_eq!(
+                escpos.render(&linear).unwrap()[8..],
+                [0b0110_0000, 0b0111_1110, 0, 0b0110_0000]
+            );
+        }
+    }
+
+    #[test]
+    fn label() {
+        use crate::render::{BarWidthReduction, LabelRenderer, PrinterLanguage};
+
+        let linear = Symbol::Linear(BitVec::from_bytes(&[0b1011_0010]));
+        let zpl = LabelRenderer {
+            dpi: 254,
+            module_width: 0.2,
+            bar_height: 10.0,
+            x_offset: 1.0,
+            y_offset: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            zpl.render(&linear),
+            "^XA\n^FO10,20^GB2,100,2^FS\n^FO14,20^GB4,100,4^FS\n^FO22,20^GB2,100,2^FS\n^XZ\n"
+        );
+        let epl = LabelRenderer {
+            language: PrinterLanguage::Epl,
+            ..zpl.clone()
+        };
+        assert!(epl.render(&linear).contains("\nLO14,20,4,100\n"));
+        let dpl = LabelRenderer {
+            language: PrinterLanguage::Dpl,
+            ..zpl.clone()
+        };
+        assert!(dpl.render(&linear).contains("\n1X1100000200014l00040100\n"));
+        let reduced = LabelRenderer {
+            bar_width_reduction: BarWidthReduction::Micrometres(100.0),
+            ..zpl.clone()
+        };
+        assert!(reduced.render(&linear).contains("^FO15,20^GB3,100,3^FS"));
+
+        let data = "5901234123457".chars().collect::<Vec<_>>();
+        let native = LabelRenderer {
+            native: true,
+            ..zpl
+        };
+        assert_eq!(
+            native.render_format(&data, BarcodeFormat::EAN13).unwrap(),
+            "^XA\n^FO10,20^BY2,,100^BEN,100,N,N^FD590123412345^FS\n^XZ\n"
+        );
+        assert!(native
+            .render_format(&data, BarcodeFormat::Code11)
+            .unwrap()
+            .contains("^GB"));
+        let itf = "1234".chars().collect::<Vec<_>>();
+        assert!(native
+            .render_format(&itf, BarcodeFormat::Interleaved2Of5)
+            .unwrap()
+            .contains("^BY2,2.0,100^B2N,"));
+        let hibc = "A123BJC5D6E71".chars().collect::<Vec<_>>();
+        assert!(native
+            .render_format(&hibc, BarcodeFormat::Hibc(format::HibcSymbology::Code39))
+            .unwrap()
+            .contains("^BY2,3.0,100^B3N,N,100,N,N^FD+A123BJC5D6E71G^FS"));
+        // the same ratio for all Code 39 formats
+        assert!(native
+            .render_format(&itf[..3], BarcodeFormat::Code32)
+            .unwrap()
+            .contains("^BY2,3.0,100^B3N,N,100,N,N^FD"));
+
+        let mode = format::MaxiCodeMode::Structured(format::CarrierMessage {
+            postal_code: "15238".into(),
+            country: 840,
+            service_class: 1,
+        });
+        let maxicode = "A^B".chars().collect::<Vec<_>>();
+        assert_eq!(
+            native
+                .render_format(&maxicode, BarcodeFormat::MaxiCode(mode.clone()))
+                .unwrap(),
+            "^XA\n^FO10,20^BD2,1,1^FH^FD001840152380000A_5EB^FS\n^XZ\n"
+        );
+        let native_epl = LabelRenderer {
+            language: PrinterLanguage::Epl,
+            ..native.clone()
+        };
+        assert!(native_epl
+            .render_format(&maxicode, BarcodeFormat::MaxiCode(mode.clone()))
+            .unwrap()
+            .contains("\nb10,20,M,c001,840,152380000,\"A^B\"\n"));
+        let native_dpl = LabelRenderer {
+            language: PrinterLanguage::Dpl,
+            ..native
+        };
+        let hexagonal = native_dpl
+            .render_format(&maxicode, BarcodeFormat::MaxiCode(mode))
+            .unwrap();
+        assert!(hexagonal.contains("\n1X11000"));
+        assert!(hexagonal.lines().count() > 33);
+    }
+
+    #[test]
+    fn escpos() {
+        use crate::render::EscPosRenderer;
+
+        let linear = Symbol::Linear(BitVec::from_bytes(&[0b1011_0010]));
+        let renderer = EscPosRenderer {
+            module_width: 2,
+            height: 2,
+            native: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            renderer.render(&linear).unwrap(),
+            [
+                0x1d,
+                b'v',
+                b'0',
+                0,
+                2,
+                0,
+                2,
+                0,
+                0b1100_1111,
+                0b0000_1100,
+                0b1100_1111,
+                0b0000_1100
+            ]
+        );
+
+        let data = "1234567".chars().collect::<Vec<_>>();
+        let native = renderer
+            .render_format(&data, BarcodeFormat::Interleaved2Of5)
+            .unwrap();
+        assert_eq!(native[9..13], [0x1d, b'k', 70, 8]);
+        assert_eq!(&native[13..], b"12345670");
+        let raster = renderer
+            .render_format(&data, BarcodeFormat::Code11)
+            .unwrap();
+        assert_eq!(raster[..4], [0x1d, b'v', b'0', 0]);
+        let pzn = renderer.render_format(&data, BarcodeFormat::Pzn).unwrap();
+        assert_eq!(pzn[9..13], [0x1d, b'k', 69, 9]);
+        assert_eq!(&pzn[13..], b"-12345678");
+        let maxicode = renderer
+            .render_format(
+                &data,
+                BarcodeFormat::MaxiCode(format::MaxiCodeMode::Standard),
+            )
+            .unwrap();
+        // 61 dots of hexagons, on 8 bytes
+        assert_eq!(maxicode[..6], [0x1d, b'v', b'0', 0, 8, 0]);
+
+        // neither truncated nor wrapped
+        let long = vec!['1'; 300];
+        assert!(matches!(
+            renderer.render_format(&long, BarcodeFormat::Interleaved2Of5),
+            Err(EncodingError::TooLong)
+        ));
+        let tall = EscPosRenderer {
+            height: 70_000,
+            ..renderer
+        };
+        assert!(matches!(tall.render(&linear), Err(EncodingError::TooLong)));
+    }
+
+    #[test]
+    fn elements() {
+        use crate::format::{Code11, ElementWidths, Fraction, Interleaved2Of5, Standard2Of5};
+
+        let data = "7501031311309".chars().collect::<Vec<_>>();
+        let ean = EAN13::elements(&data).unwrap();
+        assert_eq!(ean.characters.len(), 15);
+        assert_eq!(ean.character(0), [(true, 1), (false, 1), (true, 1)]);
+        // the second digit, 5, in odd parity
+        assert_eq!(
+            ean.character(1),
+            [(false, 1), (true, 2), (false, 3), (true, 1)]
+        );
+        assert_eq!(
+            ean.modules(),
+            BitVec::try_from(data.barcode_encode(BarcodeFormat::EAN13).unwrap()).unwrap()
+        );
+
+        let data = "12".chars().collect::<Vec<_>>();
+        let itf = Interleaved2Of5::elements(&data, ElementWidths::whole(3)).unwrap();
+        assert_eq!(itf.characters.len(), 3);
+        assert_eq!(
+            itf.character(1)
+                .iter()
+                .map(|&(_, width)| width)
+                .collect::<Vec<_>>(),
+            [3, 1, 1, 3, 1, 1, 1, 1, 3, 3]
+        );
+        assert_eq!(itf.width(), 4 + 18 + 5);
+        assert_eq!(
+            Standard2Of5::elements(&data, ElementWidths::whole(2))
+                .unwrap()
+                .width(),
+            Standard2Of5::elements(&data, Standard2Of5::WIDTHS)
+                .unwrap()
+                .width()
+                - 4
+        );
+        assert_eq!(
+            Code11::elements(&data, ElementWidths::whole(3))
+                .unwrap()
+                .width(),
+            3 * 10 + 9
+        );
+
+        // 2.5:1 wide elements and gaps of 1.5 narrow elements, in half modules
+        let widths = ElementWidths {
+            wide: Fraction::new(5, 2),
+            gap: Fraction::new(3, 2),
+        };
+        let code_11 = Code11::elements(&data, widths).unwrap();
+        assert_eq!(code_11.scale, 2);
+        assert_eq!(
+            code_11.character(1),
+            [
+                (true, 5),
+                (false, 2),
+                (true, 2),
+                (false, 2),
+                (true, 5),
+                (false, 3)
+            ]
+        );
+        assert_eq!(code_11.modules().len(), code_11.width());
+        // in half modules through the layout, not as a symbol alone
+        let options = EncodeOptions::new().widths(widths);
+        let layout = encode_layout(&data, BarcodeFormat::Code11, &options).unwrap();
+        assert_eq!(layout.scale, 2);
+        assert_eq!(BitVec::try_from(layout.symbol).unwrap(), code_11.modules());
+        assert!(matches!(
+            crate::encode_with(&data, BarcodeFormat::Code11, &options),
+            Err(EncodingError::FractionalWidths)
+        ));
+        for wide in [
+            Fraction::new(3, 2),
+            Fraction::new(7, 2),
+            Fraction::new(5, 0),
+        ] {
+            let widths = ElementWidths { wide, ..widths };
+            assert!(matches!(
+                Code11::elements(&data, widths),
+                Err(EncodingError::WrongWidths)
+            ));
+        }
+        let widths = ElementWidths {
+            gap: Fraction::new(1, 0),
+            ..widths
+        };
+        assert!(matches!(
+            Code11::elements(&data, widths),
+            Err(EncodingError::WrongWidths)
+        ));
+
+        let symbol = "55123457".barcode_encode(BarcodeFormat::EAN8).unwrap();
+        let elements = symbol.elements().unwrap();
+        assert!(elements.characters.is_empty());
+        assert_eq!(elements.elements[..3], [(true, 1), (false, 1), (true, 1)]);
+        assert_eq!(elements.width(), 67);
+    }
+
+    #[test]
+    fn encode_with() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        let modules = |symbol: Symbol| {
+            BitVec::try_from(symbol)
+                .unwrap()
+                .iter()
+                .map(|dark| if dark { '1' } else { '0' })
+                .collect::<String>()
+        };
+
+        let options = EncodeOptions::new().add_on("52495");
+        let result = super::encode_with(&chars("590123412345"), BarcodeFormat::EAN13, &options);
+        let result = modules(result.unwrap());
+        assert_eq!(result.len(), 95 + EanAddOn::GAP + 47);
+        assert_eq!(
+            result[95 + EanAddOn::GAP..],
+            *"10110111001010010011010011101010001011010110001"
+        );
+
+        let options = EncodeOptions::new().checksum(Checksum::Double);
+        let result = super::encode_with(&chars("123-45"), BarcodeFormat::Code11, &options);
+        assert_eq!(
+            modules(result.unwrap()),
+            "101100101101011010010110110010101011010101101101101101011011010100101101011001"
+        );
+        assert_eq!(Code11::c_check(&chars("123-45")), '5');
+        assert_eq!(Code11::k_check(&chars("123-455")), '2');
+
+        let options = EncodeOptions::new().checksum(Checksum::None);
+        let result =
+            super::encode_with(&chars("1234567"), BarcodeFormat::Interleaved2Of5, &options);
+        assert!(matches!(result, Err(EncodingError::WrongSize)));
+        let options = options.checksum(Checksum::Single).quiet_zone(10);
+        let result =
+            super::encode_with(&chars("1234567"), BarcodeFormat::Interleaved2Of5, &options);
+        let result = modules(result.unwrap());
+        assert_eq!(result.len(), 10 + 64 + 10);
+        assert!(result.starts_with("00000000001010"));
+        let options = options.checksum(Checksum::Double);
+        for format in [BarcodeFormat::Interleaved2Of5, BarcodeFormat::Standard2Of5] {
+            let result = super::encode_with(&chars("1234567"), format, &options);
+            assert!(matches!(result, Err(EncodingError::UnsupportedChecksum)));
+        }
+        // sums past 255
+        assert_eq!(dyn_calc_checksum(&chars("451699146827544196510496637")), 2);
+    }
+
+    #[test]
+    fn layout() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+        let options = EncodeOptions::new().include_quiet_zone(true);
+        let result = encode_layout(&chars("7501031311309"), BarcodeFormat::EAN13, &options);
+        let result = result.unwrap();
+        assert_eq!(result.quiet_zone, QuietZone { left: 11, right: 7 });
+        assert_eq!(result.symbol.width(), 11 + 95 + 7);
+        assert_eq!(result.guards, [11..14, 56..61, 103..106]);
+        assert_eq!(result.characters.len(), 12);
+        assert_eq!(result.characters[..2], [14..21, 21..28]);
+
+        let options = options.add_on("12");
+        let result = encode_layout(&chars("5512345"), BarcodeFormat::EAN8, &options).unwrap();
+        assert_eq!(result.quiet_zone, QuietZone { left: 7, right: 5 });
+        assert_eq!(result.symbol.width(), 7 + 67 + EanAddOn::GAP + 20 + 5);
+        assert_eq!(result.guards, [7..10, 38..43, 71..74]);
+        let add_on = 74 + EanAddOn::GAP + 4;
+        assert_eq!(
+            result.characters[8..],
+            [add_on..add_on + 7, add_on + 9..add_on + 16]
+        );
+
+        let result = encode_layout(&chars("12"), BarcodeFormat::Code11, &EncodeOptions::new());
+        let result = result.unwrap();
+        assert!(result.guards.is_empty());
+        assert_eq!(result.characters, [8..16, 16..24]);
+
+        let options = EncodeOptions::new().quiet_zone(2).include_quiet_zone(true);
+        let result = encode_layout(&chars("ABC"), BarcodeFormat::Code16K, &options).unwrap();
+        let plain = encode(&chars("ABC"), BarcodeFormat::Code16K).unwrap();
+        assert_eq!(result.symbol.width(), 10 + plain.width() + 2);
+        assert!(result.characters.is_empty());
+
+        // 2.5:1 wide elements and gaps of 1.5 narrow elements, in half modules
+        let widths = format::ElementWidths {
+            wide: format::Fraction::new(5, 2),
+            gap: format::Fraction::new(3, 2),
+        };
+        let fractional = EncodeOptions::new().widths(widths).include_quiet_zone(true);
+        let result = encode_layout(&chars("12"), BarcodeFormat::Code11, &fractional).unwrap();
+        assert_eq!(result.scale, 2);
+        // narrow elements of 2 units, wide ones of 5 and gaps of 3, the start character
+        // taking 19 of them after the 20 of the quiet zone
+        assert_eq!(result.characters, [39..58, 58..77]);
+        assert_eq!(result.symbol.width(), 20 + 3 * 19 + 16 + 20);
+
+        let composite = BarcodeFormat::Composite(CompositeLinear::EAN13);
+        let result = encode_layout(&chars("331234567890|(99)1234-abcd"), composite, &options);
+        let result = result.unwrap();
+        // the linear component past the quiet zone, a light module and the 2 of its shift
+        assert_eq!(result.guards, [14..17, 59..64, 106..109]);
+        assert_eq!(result.characters.len(), 12);
+        assert_eq!(result.characters[6], 64..71);
+
+        let format = BarcodeFormat::HanXin(format::HanXinEccLevel::L1);
+        assert_eq!(format.quiet_zone(), QuietZone::both(3));
+        let result = encode_layout(&chars("汉信码"), format, &options).unwrap();
+        let format = BarcodeFormat::HanXin(format::HanXinEccLevel::L1);
+        let plain = encode(&chars("汉信码"), format).unwrap();
+        // light modules all around
+        assert_eq!((result.symbol.width(), result.symbol.height()), (29, 29));
+        assert!(!result.symbol.get(2, 3) && result.symbol.get(3, 3) && plain.get(0, 0));
+        assert_eq!(result.symbol.get(25, 25), plain.get(22, 22));
+
+        let maxicode = || BarcodeFormat::MaxiCode(format::MaxiCodeMode::Standard);
+        assert!(matches!(
+            encode_layout(&chars("ABC"), maxicode(), &options),
+            Err(EncodingError::UnsupportedQuietZone)
+        ));
+        assert!(encode_layout(&chars("ABC"), maxicode(), &EncodeOptions::new()).is_ok());
+        assert!(matches!(
+            encode_layout(&chars("12345"), BarcodeFormat::Postnet, &options),
+            Err(EncodingError::UnsupportedQuietZone)
+        ));
+    }
+
+    #[test]
+    fn symbol() {
+        let result = "12345670"
+            .barcode_encode(BarcodeFormat::Interleaved2Of5)
+            .unwrap();
+        assert_eq!((result.width(), result.height()), (64, 1));
+        let modules = result.iter().map(|(_, _, dark)| dark).collect::<BitVec>();
+        assert_eq!(Some(&modules), result.as_linear());
+
+        let result = "汉信码".barcode_encode(BarcodeFormat::HanXin(format::HanXinEccLevel::L1));
+        let result = result.unwrap();
+        assert_eq!((result.width(), result.height()), (23, 23));
+        assert!(result.get(0, 0) && !result.get(1, 1));
+        assert!(BitVec::try_from(result).is_err());
+
+        let result = "ABC".barcode_encode(BarcodeFormat::MaxiCode(format::MaxiCodeMode::Standard));
+        let result = result.unwrap();
+        assert!(matches!(result, Symbol::Hexagonal(_)));
+        assert!(!result.get(30, 0) && !result.get(0, 33));
+
+        let result = Symbol::FourState(vec![
+            FourStateBar::Full,
+            FourStateBar::Ascender,
+            FourStateBar::Descender,
+            FourStateBar::Tracker,
+        ]);
+        assert_eq!((result.width(), result.height()), (7, 3));
+        let rows = (0..3)
+            .map(|y| {
+                (0..7)
+                    .map(|x| if result.get(x, y) { '1' } else { '0' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, ["1010000", "1010101", "1000100"]);
+    }
 }
@@ -0,0 +1,76 @@
+use crate::format::ElementWidths;
+
+/// Check characters added by [`encode_with`](crate::encode_with)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Checksum {
+    /// The format's own check characters: the EAN check digit computed or verified, the
+    /// Interleaved 2 of 5 check digit for an odd number of digits and none for Code 11 and
+    /// Standard 2 of 5
+    #[default]
+    Default,
+    /// No check character, Interleaved 2 of 5 taking an even number of digits
+    None,
+    /// The modulo 10 check digit of 2 of 5, a leading zero keeping Interleaved 2 of 5 even,
+    /// or the `C` check character of Code 11
+    Single,
+    /// The `C` and `K` check characters of Code 11, 2 of 5 returning
+    /// [`EncodingError::UnsupportedChecksum`](crate::EncodingError::UnsupportedChecksum)
+    Double,
+}
+
+/// Options of [`encode_with`](crate::encode_with), each of them ignored by the formats it does
+/// not apply to, the defaults giving the output of [`encode`](crate::encode)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EncodeOptions {
+    /// Check characters of Code 11 and 2 of 5
+    pub checksum: Checksum,
+    /// Wide to narrow ratio and gap of Code 11 and 2 of 5, the format's own when [`None`],
+    /// fractional widths only laid out by [`encode_layout`](crate::encode_layout)
+    pub widths: Option<ElementWidths>,
+    /// 2 or 5 digit add-on of EAN-13 and EAN-8
+    pub add_on: Option<String>,
+    /// Light modules added on each side of linear and stacked symbols and all around matrix
+    /// symbols, in whole modules, MaxiCode and 4-state and 2-state bars giving
+    /// [`EncodingError::UnsupportedQuietZone`](crate::EncodingError::UnsupportedQuietZone)
+    pub quiet_zone: usize,
+    /// Widen the light modules on each side to the quiet zone required by the symbology, in
+    /// modules as [`BarcodeFormat::quiet_zone`](crate::BarcodeFormat::quiet_zone)
+    pub include_quiet_zone: bool,
+}
+
+impl EncodeOptions {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the check characters
+    pub fn checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Set the wide to narrow ratio and gap
+    pub fn widths(mut self, widths: ElementWidths) -> Self {
+        self.widths = Some(widths);
+        self
+    }
+
+    /// Set the add-on digits
+    pub fn add_on(mut self, add_on: &str) -> Self {
+        self.add_on = Some(add_on.to_string());
+        self
+    }
+
+    /// Set the light modules on each side
+    pub fn quiet_zone(mut self, quiet_zone: usize) -> Self {
+        self.quiet_zone = quiet_zone;
+        self
+    }
+
+    /// Set whether the quiet zone required by the symbology is included
+    pub fn include_quiet_zone(mut self, include_quiet_zone: bool) -> Self {
+        self.include_quiet_zone = include_quiet_zone;
+        self
+    }
+}
@@ -0,0 +1,115 @@
+use super::{bars, hexagon_rows, module_rows, runs, BarWidthReduction, NativeFormat};
+use crate::{BarcodeFormat, EncodingError, Symbol};
+
+const GS: u8 = 0x1d;
+
+/// Renders a [`Symbol`] as ESC/POS receipt printer commands, a raster bit image of its modules
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscPosRenderer {
+    /// Width of a module, in dots, from 1 to 6 for the printer's own barcodes
+    pub module_width: usize,
+    /// Height of the bars of linear symbols, in dots, at most 255 for the printer's own
+    /// barcodes
+    pub height: usize,
+    /// Resolution of the printer, in dots per inch
+    pub dpi: u32,
+    /// Width taken off every bar of the raster bit image
+    pub bar_width_reduction: BarWidthReduction,
+    /// Use the printer's `GS k` barcode command for the formats it supports, in
+    /// [`EscPosRenderer::render_format`]
+    pub native: bool,
+}
+
+impl Default for EscPosRenderer {
+    fn default() -> Self {
+        Self {
+            module_width: 2,
+            height: 80,
+            dpi: 203,
+            bar_width_reduction: BarWidthReduction::None,
+            native: false,
+        }
+    }
+}
+
+impl EscPosRenderer {
+    /// `GS v 0` raster bit image of the symbol, each module row taking as many dot rows as a
+    /// module is wide, or of the hexagons and bullseye rings of MaxiCode, the bar width
+    /// reduction not applying to it.
+    ///
+    /// Images of more than 65535 bytes per row or dot rows give [`EncodingError::TooLong`].
+    pub fn render(&self, symbol: &Symbol) -> Result<Vec<u8>, EncodingError> {
+        let module = self.module_width.max(1);
+        let reduction = self.bar_width_reduction.dots(self.dpi as f64);
+        let (rows, module, scale, reduction) = match symbol {
+            Symbol::Hexagonal(maxicode) => (hexagon_rows(maxicode, module, 0), 1, 1, 0.0),
+            Symbol::Linear(_) => (module_rows(symbol, 0, 1), module, self.height, reduction),
+            _ => (module_rows(symbol, 0, 1), module, module, reduction),
+        };
+        let width = rows.first().map_or(0, Vec::len) * module;
+        let bytes_per_row = width.div_ceil(8);
+        let height = rows.len() * scale;
+
+        let size = |len: usize| u16::try_from(len).map_err(|_| EncodingError::TooLong);
+        let mut commands = vec![GS, b'v', b'0', 0];
+        commands.extend(size(bytes_per_row)?.to_le_bytes());
+        commands.extend(size(height)?.to_le_bytes());
+        for row in &rows {
+            let mut bytes = vec![0u8; bytes_per_row];
+            for (left, bar_width) in bars(&runs(row.iter().copied()), module as f64, reduction) {
+                for x in left.round() as usize..(left + bar_width).round() as usize {
+                    bytes[x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+            for _ in 0..scale {
+                commands.extend(&bytes);
+            }
+        }
+        Ok(commands)
+    }
+
+    /// Commands printing the data encoded in the format, with the printer's `GS k` command for
+    /// EAN-13, EAN-8, ITF and the Code 39 of PZN, Code 32 and HIBC when
+    /// [`EscPosRenderer::native`] is set, or as a raster bit image otherwise.
+    ///
+    /// Data of more than 255 characters for `GS k` gives [`EncodingError::TooLong`], as do
+    /// oversized raster bit images.
+    pub fn render_format(
+        &self,
+        data: &[char],
+        format: BarcodeFormat,
+    ) -> Result<Vec<u8>, EncodingError> {
+        let native = NativeFormat::new(&format)
+            .filter(|native| self.native && !matches!(native, NativeFormat::MaxiCode(_)));
+        let symbol = crate::encode(data, format)?;
+        let Some(native) = native else {
+            return self.render(&symbol);
+        };
+
+        let data = native.data(data);
+        let kind = match native {
+            NativeFormat::Ean13 => 67,
+            NativeFormat::Ean8 => 68,
+            NativeFormat::Code39(_) => 69,
+            _ => 70,
+        };
+        let mut commands = vec![
+            GS,
+            b'h',
+            self.height.clamp(1, 255) as u8,
+            GS,
+            b'w',
+            self.module_width.clamp(1, 6) as u8,
+            // no human readable text
+            GS,
+            b'H',
+            0,
+            GS,
+            b'k',
+            kind,
+            u8::try_from(data.len()).map_err(|_| EncodingError::TooLong)?,
+        ];
+        commands.extend(data.bytes());
+        Ok(commands)
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt::Write;
+
+use super::{bars, hexagon_rows, runs, BarWidthReduction, NativeFormat};
+use crate::{format::MaxiCodeMode, BarcodeFormat, EncodingError, Symbol};
+
+/// Command language of a label printer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrinterLanguage {
+    /// Zebra Programming Language
+    Zpl,
+    /// Eltron Programming Language
+    Epl,
+    /// Datamax Programming Language, positions and sizes in tenths of millimetres
+    Dpl,
+}
+
+/// Renders a [`Symbol`] as label printer commands, its dark modules drawn as filled boxes, or
+/// the dark dots of MaxiCode hexagons and bullseye rings a module width wide
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelRenderer {
+    /// Command language of the printer
+    pub language: PrinterLanguage,
+    /// Resolution of the printer, in dots per inch
+    pub dpi: u32,
+    /// Width of a module, the X-dimension, in millimetres, rounded to whole dots
+    pub module_width: f64,
+    /// Height of the bars of linear symbols, in millimetres
+    pub bar_height: f64,
+    /// Width taken off every bar drawn as boxes
+    pub bar_width_reduction: BarWidthReduction,
+    /// Distance of the symbol from the left edge of the label, in millimetres
+    pub x_offset: f64,
+    /// Distance of the symbol from the top edge of the label, or from the bottom edge in DPL,
+    /// in millimetres
+    pub y_offset: f64,
+    /// Use the printer's own barcode command for the formats it supports, in
+    /// [`LabelRenderer::render_format`]
+    pub native: bool,
+}
+
+impl Default for LabelRenderer {
+    fn default() -> Self {
+        Self {
+            language: PrinterLanguage::Zpl,
+            dpi: 203,
+            module_width: 0.33,
+            bar_height: 15.0,
+            bar_width_reduction: BarWidthReduction::None,
+            x_offset: 0.0,
+            y_offset: 0.0,
+            native: false,
+        }
+    }
+}
+
+/// Box as `x y width height` in dots, from its upper left corner
+type DotBox = [usize; 4];
+
+impl LabelRenderer {
+    /// Label of the symbol drawn as filled boxes, one for each run of dark modules of a row,
+    /// or of dark dots of a row of MaxiCode, the bar width reduction not applying to it
+    pub fn render(&self, symbol: &Symbol) -> String {
+        let module = self.module_dots();
+        if let Symbol::Hexagonal(maxicode) = symbol {
+            let rows = hexagon_rows(maxicode, module, 0);
+            let mut label = self.start();
+            for (y, row) in rows.iter().enumerate() {
+                for (left, width) in bars(&runs(row.iter().copied()), 1.0, 0.0) {
+                    let dot_box = [left as usize, y, width as usize, 1];
+                    self.push_box(&mut label, dot_box, rows.len());
+                }
+            }
+            label.push_str(self.end());
+            return label;
+        }
+
+        let rows = match symbol {
+            Symbol::Linear(_) => vec![(0, self.dots(self.bar_height))],
+            Symbol::Stacked(stacked) => stacked
+                .iter()
+                .enumerate()
+                .map(|(y, row)| (y, row.height * module))
+                .collect(),
+            _ => (0..symbol.height()).map(|y| (y, module)).collect(),
+        };
+        let height = rows.iter().map(|&(_, height)| height).sum::<usize>();
+
+        let reduction = self.bar_width_reduction.dots(self.dpi as f64);
+        let mut boxes = Vec::new();
+        let mut top = 0;
+        for (y, row_height) in rows {
+            let runs = runs((0..symbol.width()).map(|x| symbol.get(x, y)));
+            for (left, width) in bars(&runs, module as f64, reduction) {
+                let (left, right) = (left.round() as usize, (left + width).round() as usize);
+                if right > left {
+                    boxes.push([left, top, right - left, row_height]);
+                }
+            }
+            top += row_height;
+        }
+
+        let mut label = self.start();
+        for dot_box in boxes {
+            self.push_box(&mut label, dot_box, height);
+        }
+        label.push_str(self.end());
+        label
+    }
+
+    /// Label of the data encoded in the format, with the printer's own barcode command when
+    /// [`LabelRenderer::native`] is set and the printer supports the format, EAN-13, EAN-8,
+    /// Interleaved 2 of 5, the Code 39 of PZN, Code 32 and HIBC, and MaxiCode in ZPL and
+    /// modes 2 to 4 of it in EPL, or drawn as boxes otherwise
+    pub fn render_format(
+        &self,
+        data: &[char],
+        format: BarcodeFormat,
+    ) -> Result<String, EncodingError> {
+        let native = NativeFormat::new(&format).filter(|native| self.supports(native));
+        let symbol = crate::encode(data, format)?;
+        let Some(native) = native else {
+            return Ok(self.render(&symbol));
+        };
+
+        let mut label = self.start();
+        self.push_native(&mut label, &native, &native.data(data));
+        label.push_str(self.end());
+        Ok(label)
+    }
+
+    /// Whether the printer's own barcode command is used for the format
+    fn supports(&self, format: &NativeFormat) -> bool {
+        use MaxiCodeMode::*;
+
+        self.native
+            && match (self.language, format) {
+                (PrinterLanguage::Zpl, _) => true,
+                (PrinterLanguage::Epl, NativeFormat::MaxiCode(mode)) => {
+                    matches!(mode, Structured(_) | Standard)
+                }
+                (PrinterLanguage::Dpl, NativeFormat::MaxiCode(_)) => false,
+                _ => true,
+            }
+    }
+
+    /// Length in millimetres as dots
+    fn dots(&self, millimetres: f64) -> usize {
+        (millimetres * self.dpi as f64 / 25.4).round() as usize
+    }
+
+    /// Width of a module in dots, at least one
+    fn module_dots(&self) -> usize {
+        self.dots(self.module_width).max(1)
+    }
+
+    /// Length in dots as tenths of millimetres
+    fn dpl_units(&self, dots: usize) -> usize {
+        (dots as f64 * 254.0 / self.dpi as f64).round() as usize
+    }
+
+    /// Commands opening a label
+    fn start(&self) -> String {
+        match self.language {
+            PrinterLanguage::Zpl => "^XA\n",
+            PrinterLanguage::Epl => "N\n",
+            PrinterLanguage::Dpl => "\x02L\nm\nD11\n",
+        }
+        .to_string()
+    }
+
+    /// Commands printing the label
+    fn end(&self) -> &'static str {
+        match self.language {
+            PrinterLanguage::Zpl => "^XZ\n",
+            PrinterLanguage::Epl => "P1\n",
+            PrinterLanguage::Dpl => "E\n",
+        }
+    }
+
+    /// Append a filled box, DPL rows growing upwards from the bottom of a symbol of the given
+    /// height
+    fn push_box(&self, label: &mut String, [x, y, width, height]: DotBox, symbol_height: usize) {
+        let (left, top) = (self.dots(self.x_offset) + x, self.dots(self.y_offset) + y);
+        let _ = match self.language {
+            PrinterLanguage::Zpl => writeln!(
+                label,
+                "^FO{left},{top}^GB{width},{height},{}^FS",
+                width.min(height)
+            ),
+            PrinterLanguage::Epl => writeln!(label, "LO{left},{top},{width},{height}"),
+            PrinterLanguage::Dpl => {
+                let row = self.dots(self.y_offset) + symbol_height - y - height;
+                writeln!(
+                    label,
+                    "1X11000{:04}{:04}l{:04}{:04}",
+                    self.dpl_units(row),
+                    self.dpl_units(left),
+                    self.dpl_units(width),
+                    self.dpl_units(height)
+                )
+            }
+        };
+    }
+
+    /// Append the printer's own barcode command, without human readable text
+    fn push_native(&self, label: &mut String, format: &NativeFormat, data: &str) {
+        let (left, top) = (self.dots(self.x_offset), self.dots(self.y_offset));
+        if let NativeFormat::MaxiCode(mode) = format {
+            self.push_maxicode(label, mode, data);
+            return;
+        }
+        let (narrow, height) = (self.module_dots(), self.dots(self.bar_height));
+        let ratio = format.ratio().unwrap_or(1);
+        let _ = match self.language {
+            PrinterLanguage::Zpl => {
+                let command = match format {
+                    NativeFormat::Ean13 => "^BEN,",
+                    NativeFormat::Ean8 => "^B8N,",
+                    NativeFormat::Code39(_) => "^B3N,N,",
+                    _ => "^B2N,",
+                };
+                // the ratio only applies to formats with wide elements, from 2.0 to 3.0
+                let ratio = format
+                    .ratio()
+                    .map_or(String::new(), |ratio| format!("{}.0", ratio.clamp(2, 3)));
+                writeln!(
+                    label,
+                    "^FO{left},{top}^BY{narrow},{ratio},{height}{command}{height},N,N^FD{data}^FS"
+                )
+            }
+            PrinterLanguage::Epl => {
+                let kind = match format {
+                    NativeFormat::Ean13 => "E30",
+                    NativeFormat::Ean8 => "E80",
+                    NativeFormat::Code39(_) => "3",
+                    _ => "2",
+                };
+                writeln!(
+                    label,
+                    "B{left},{top},0,{kind},{narrow},{},{height},N,\"{data}\"",
+                    ratio * narrow
+                )
+            }
+            PrinterLanguage::Dpl => {
+                let id = match format {
+                    NativeFormat::Ean13 => 'F',
+                    NativeFormat::Ean8 => 'G',
+                    NativeFormat::Code39(_) => 'A',
+                    _ => 'D',
+                };
+                writeln!(
+                    label,
+                    "1{id}{}{}{:03}{:04}{:04}{data}",
+                    dpl_width(ratio * narrow),
+                    dpl_width(narrow),
+                    self.dpl_units(height),
+                    self.dpl_units(top),
+                    self.dpl_units(left)
+                )
+            }
+        };
+    }
+
+    /// Append the printer's own MaxiCode command, the structured carrier message of modes 2
+    /// and 3 leading the data in ZPL and taking parameters of its own in EPL
+    fn push_maxicode(&self, label: &mut String, mode: &MaxiCodeMode, data: &str) {
+        let (left, top) = (self.dots(self.x_offset), self.dots(self.y_offset));
+        let carrier = match mode {
+            MaxiCodeMode::Structured(carrier) => Some(carrier),
+            _ => None,
+        };
+        // numeric postal codes of mode 2, a US one completed by a "+4" of zeroes
+        let postal_code = carrier.map(|carrier| {
+            let numeric = carrier.postal_code.chars().all(|c| c.is_ascii_digit());
+            match carrier.postal_code.len() {
+                5 if numeric && carrier.country == 840 => format!("{}0000", carrier.postal_code),
+                _ if numeric => carrier.postal_code.clone(),
+                _ => format!("{:<6}", carrier.postal_code),
+            }
+        });
+        let _ = match self.language {
+            PrinterLanguage::Zpl => {
+                let (mode, primary) = match (mode, carrier, &postal_code) {
+                    (MaxiCodeMode::Structured(_), Some(carrier), Some(postal_code)) => (
+                        if postal_code.len() == 6 { 3 } else { 2 },
+                        format!(
+                            "{:03}{:03}{postal_code}",
+                            carrier.service_class, carrier.country
+                        ),
+                    ),
+                    (MaxiCodeMode::EnhancedEcc, ..) => (5, String::new()),
+                    (MaxiCodeMode::ReaderProgramming, ..) => (6, String::new()),
+                    _ => (4, String::new()),
+                };
+                writeln!(
+                    label,
+                    "^FO{left},{top}^BD{mode},1,1^FH^FD{}{}^FS",
+                    zpl_escape(&primary),
+                    zpl_escape(data)
+                )
+            }
+            _ => {
+                let carrier = carrier
+                    .zip(postal_code)
+                    .map(|(carrier, postal_code)| {
+                        format!(
+                            "c{:03},{:03},{},",
+                            carrier.service_class,
+                            carrier.country,
+                            postal_code.trim_end()
+                        )
+                    })
+                    .unwrap_or_default();
+                let data = data.replace('\\', "\\\\").replace('"', "\\\"");
+                writeln!(label, "b{left},{top},M,{carrier}\"{data}\"")
+            }
+        };
+    }
+}
+
+/// ZPL field data with the control characters and `^`, `~` and `_` as `_` and their hex
+/// value, for a field preceded by `^FH`
+fn zpl_escape(data: &str) -> String {
+    data.chars()
+        .map(|c| match c {
+            '\0'..='\x1f' | '^' | '~' | '_' => format!("_{:02X}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Element width in dots as a DPL width character, `0` to `9` then `A` to `Z`
+fn dpl_width(dots: usize) -> char {
+    char::from_digit(dots.min(35) as u32, 36)
+        .unwrap_or('0')
+        .to_ascii_uppercase()
+}
@@ -0,0 +1,220 @@
+mod escpos;
+mod label;
+mod terminal;
+mod vector;
+
+pub use escpos::EscPosRenderer;
+pub use label::{LabelRenderer, PrinterLanguage};
+pub use terminal::TerminalRenderer;
+pub use vector::VectorRenderer;
+
+use crate::{
+    format::{
+        dyn_calc_checksum, Code32, Hibc, HibcSymbology, MaxiCodeMode, MaxiCodeSymbol, Pzn,
+        CODE_39_RATIO,
+    },
+    BarcodeFormat, Elements, Symbol,
+};
+
+/// Rows of modules of a symbol surrounded by a quiet zone, linear symbols taking
+/// `linear_height` rows and stacked rows repeated as many times as their height
+pub(crate) fn module_rows(
+    symbol: &Symbol,
+    quiet_zone: usize,
+    linear_height: usize,
+) -> Vec<Vec<bool>> {
+    let width = symbol.width();
+    let row = |y| {
+        let mut row = vec![false; width + 2 * quiet_zone];
+        for x in 0..width {
+            row[quiet_zone + x] = symbol.get(x, y);
+        }
+        row
+    };
+    let quiet_row = vec![false; width + 2 * quiet_zone];
+
+    let mut rows = vec![quiet_row.clone(); quiet_zone];
+    match symbol {
+        Symbol::Linear(_) => rows.extend(std::iter::repeat_n(row(0), linear_height)),
+        Symbol::Stacked(stacked) => {
+            for (y, stacked_row) in stacked.iter().enumerate() {
+                rows.extend(std::iter::repeat_n(row(y), stacked_row.height));
+            }
+        }
+        _ => rows.extend((0..symbol.height()).map(row)),
+    }
+    rows.extend(std::iter::repeat_n(quiet_row, quiet_zone));
+    rows
+}
+
+/// Rows of square pixels of a MaxiCode symbol, `scale` of them to a module width, drawing
+/// the hexagons at their offset centres and the rings of the bullseye, surrounded by `margin`
+/// light pixels
+pub(crate) fn hexagon_rows(symbol: &MaxiCodeSymbol, scale: usize, margin: usize) -> Vec<Vec<bool>> {
+    let scale = scale.max(1) as f64;
+    let (width, height) = symbol.size();
+    let bullseye = symbol.bullseye();
+    let row_height = 3f64.sqrt() / 2.0;
+    let first_center = 1.0 / 3f64.sqrt();
+
+    let dark = |x: f64, y: f64| {
+        let distance = (x - bullseye.center.0).hypot(y - bullseye.center.1);
+        if distance < bullseye.radii[0] {
+            // dark rings start at the even radii
+            return bullseye.radii.iter().filter(|&&r| distance < r).count() % 2 == 1;
+        }
+        let nearest = ((y - first_center) / row_height).round() as isize;
+        (nearest - 1..=nearest + 1)
+            .filter(|&row| row >= 0)
+            .map(|row| row as usize)
+            .any(|row| {
+                let offset = if row % 2 == 1 { 0.5 } else { 0.0 };
+                let column = (x - offset).floor();
+                if column < 0.0 {
+                    return false;
+                }
+                let (center_x, center_y) = MaxiCodeSymbol::module_center(column as usize, row);
+                let (dx, dy) = ((x - center_x).abs(), (y - center_y).abs());
+                // inside the hexagon, one module wide between its vertical sides
+                dx <= 0.5 && dx / 2.0 + dy * row_height <= 0.5 && symbol.get(column as usize, row)
+            })
+    };
+
+    let columns = (width * scale).ceil() as usize;
+    let mut rows = vec![vec![false; columns + 2 * margin]; margin];
+    for y in 0..(height * scale).ceil() as usize {
+        let mut row = vec![false; columns + 2 * margin];
+        for x in 0..columns {
+            row[margin + x] = dark((x as f64 + 0.5) / scale, (y as f64 + 0.5) / scale);
+        }
+        rows.push(row);
+    }
+    rows.extend(std::iter::repeat_n(
+        vec![false; columns + 2 * margin],
+        margin,
+    ));
+    rows
+}
+
+/// Width taken off every bar to make up for the ink spreading as it is printed, the bar
+/// keeping its center so that the spaces around it widen as much.
+///
+/// Spaces only widen by what the bars around them lose, there is no separate space gain.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BarWidthReduction {
+    /// Bars printed at their full width
+    #[default]
+    None,
+    /// Reduction in micrometres
+    Micrometres(f64),
+    /// Reduction in printer dots, or in points for vector output
+    Dots(usize),
+}
+
+impl BarWidthReduction {
+    /// Reduction in dots of a printer of the given resolution
+    pub(crate) fn dots(self, dpi: f64) -> f64 {
+        match self {
+            BarWidthReduction::None => 0.0,
+            BarWidthReduction::Micrometres(micrometres) => micrometres * dpi / 25400.0,
+            BarWidthReduction::Dots(dots) => dots as f64,
+        }
+    }
+}
+
+/// Runs of modules, as whether they are dark and their length
+pub(crate) fn runs(modules: impl IntoIterator<Item = bool>) -> Vec<(bool, usize)> {
+    modules.into_iter().collect::<Elements>().elements
+}
+
+/// Left edges and widths of the bars of the runs, modules being `module` wide and bars
+/// narrowed by `reduction` around their center
+pub(crate) fn bars(runs: &[(bool, usize)], module: f64, reduction: f64) -> Vec<(f64, f64)> {
+    let mut bars = Vec::new();
+    let mut x = 0.0;
+    for &(dark, len) in runs {
+        let width = len as f64 * module;
+        if dark {
+            let reduction = reduction.min(width);
+            bars.push((x + reduction / 2.0, width - reduction));
+        }
+        x += width;
+    }
+    bars
+}
+
+/// Formats with a barcode command of the printers' own
+#[derive(Debug, Clone)]
+pub(crate) enum NativeFormat {
+    Ean13,
+    Ean8,
+    Interleaved2Of5,
+    Code39(Code39Format),
+    MaxiCode(MaxiCodeMode),
+}
+
+/// Formats rendered in Code 39, with the digits of the PZN product number
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Code39Format {
+    Pzn(usize),
+    Code32,
+    Hibc,
+}
+
+impl NativeFormat {
+    pub(crate) fn new(format: &BarcodeFormat) -> Option<Self> {
+        match format {
+            BarcodeFormat::EAN13 => Some(NativeFormat::Ean13),
+            BarcodeFormat::EAN8 => Some(NativeFormat::Ean8),
+            BarcodeFormat::Interleaved2Of5 => Some(NativeFormat::Interleaved2Of5),
+            BarcodeFormat::Pzn
+            | BarcodeFormat::Pzn7
+            | BarcodeFormat::Code32
+            | BarcodeFormat::Hibc(HibcSymbology::Code39) => {
+                Some(NativeFormat::Code39(match format {
+                    BarcodeFormat::Pzn => Code39Format::Pzn(7),
+                    BarcodeFormat::Pzn7 => Code39Format::Pzn(6),
+                    BarcodeFormat::Code32 => Code39Format::Code32,
+                    _ => Code39Format::Hibc,
+                }))
+            }
+            BarcodeFormat::MaxiCode(mode) => Some(NativeFormat::MaxiCode(mode.clone())),
+            _ => None,
+        }
+    }
+
+    /// Width of the wide elements, in narrow ones, for the formats with wide elements
+    pub(crate) fn ratio(&self) -> Option<usize> {
+        match self {
+            NativeFormat::Interleaved2Of5 => Some(2),
+            NativeFormat::Code39(_) => Some(CODE_39_RATIO),
+            _ => None,
+        }
+    }
+
+    /// Data sent to the printer for data already encoded successfully.
+    ///
+    /// The printers compute the EAN check digit, and pad odd Interleaved 2 of 5 data with a
+    /// leading zero rather than a check digit. Code 39 data is sent without start and stop
+    /// characters, the printers adding them.
+    pub(crate) fn data(&self, data: &[char]) -> String {
+        let mut data = data.to_vec();
+        match self {
+            NativeFormat::Ean13 => data.truncate(12),
+            NativeFormat::Ean8 => data.truncate(7),
+            NativeFormat::Interleaved2Of5 if data.len() % 2 == 1 => {
+                data.push(char::from(b'0' + dyn_calc_checksum(&data)));
+            }
+            NativeFormat::Code39(format) => {
+                data = match format {
+                    Code39Format::Pzn(len) => Pzn::chars(&data, *len),
+                    Code39Format::Code32 => Code32::chars(&data),
+                    Code39Format::Hibc => Hibc::chars(&data),
+                }
+                .unwrap_or_default();
+            }
+            NativeFormat::Interleaved2Of5 | NativeFormat::MaxiCode(_) => {}
+        }
+        data.into_iter().collect()
+    }
+}
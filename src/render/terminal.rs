@@ -0,0 +1,72 @@
+use super::{hexagon_rows, module_rows};
+use crate::Symbol;
+
+/// Renders a [`Symbol`] as lines of Unicode block characters, each character covering one
+/// module column and two module rows, or two square pixels of a MaxiCode symbol drawn two
+/// pixels to a module width
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalRenderer {
+    /// Width of the light margin around the symbol, in modules
+    pub quiet_zone: usize,
+    /// Height of the bars of linear symbols, in lines
+    pub bar_height: usize,
+    /// Draw dark modules as blanks and light ones as blocks, for light text on a dark
+    /// background
+    pub invert: bool,
+    /// Human readable text centered below the symbol
+    pub text: Option<String>,
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        Self {
+            quiet_zone: 4,
+            bar_height: 4,
+            invert: false,
+            text: None,
+        }
+    }
+}
+
+impl TerminalRenderer {
+    /// Render the symbol, a line per pair of module rows, with the text on its own line
+    pub fn render(&self, symbol: &Symbol) -> String {
+        let mut rows = match symbol {
+            // the half blocks being about square, the hexagons keep their shape
+            Symbol::Hexagonal(maxicode) => hexagon_rows(maxicode, 2, 2 * self.quiet_zone),
+            _ => module_rows(symbol, self.quiet_zone, 2 * self.bar_height),
+        };
+        let width = rows.first().map_or(0, Vec::len);
+        let light = vec![false; width];
+        // an even margin keeps the symbol starting on a line
+        if self.quiet_zone % 2 == 1 && !matches!(symbol, Symbol::Hexagonal(_)) {
+            rows.insert(0, light.clone());
+            rows.push(light.clone());
+        }
+
+        let mut lines = rows
+            .chunks(2)
+            .map(|pair| {
+                let (top, bottom) = (&pair[0], pair.get(1).unwrap_or(&light));
+                top.iter()
+                    .zip(bottom)
+                    .map(
+                        |(&top, &bottom)| match (top ^ self.invert, bottom ^ self.invert) {
+                            (true, true) => '█',
+                            (true, false) => '▀',
+                            (false, true) => '▄',
+                            (false, false) => ' ',
+                        },
+                    )
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(text) = &self.text {
+            let len = text.chars().count();
+            let indent = width.saturating_sub(len) / 2;
+            lines.push(format!("{}{text}", " ".repeat(indent)));
+        }
+        lines.join("\n")
+    }
+}
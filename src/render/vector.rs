@@ -0,0 +1,334 @@
+use std::fmt::Write;
+
+use super::{bars, runs, BarWidthReduction};
+use crate::{format::MaxiCodeSymbol, Symbol};
+
+/// Renders a [`Symbol`] as filled rectangles in PostScript points, to EPS or PDF, MaxiCode
+/// symbols being drawn as hexagons around the rings of their bullseye
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorRenderer {
+    /// Width of a module, the X-dimension, in points
+    pub module_width: f64,
+    /// Height of the bars of linear symbols, in points
+    pub bar_height: f64,
+    /// Width of the light margin around the symbol, in modules
+    pub quiet_zone: usize,
+    /// Width taken off every bar, [`BarWidthReduction::Dots`] being points
+    pub bar_width_reduction: BarWidthReduction,
+    /// Human readable text centered below the symbol, in Helvetica
+    pub text: Option<String>,
+    /// Size of the text, in points
+    pub font_size: f64,
+}
+
+impl Default for VectorRenderer {
+    fn default() -> Self {
+        Self {
+            module_width: 1.0,
+            bar_height: 72.0,
+            quiet_zone: 10,
+            bar_width_reduction: BarWidthReduction::None,
+            text: None,
+            font_size: 10.0,
+        }
+    }
+}
+
+const POINTS_PER_INCH: f64 = 72.0;
+
+/// Width of the Helvetica digits as a fraction of the font size, used to center text in PDF
+const HELVETICA_DIGIT_WIDTH: f64 = 0.556;
+
+/// Rectangle as `x y width height`, from its lower left corner
+type Rect = [f64; 4];
+
+/// Hexagon as its vertices
+type Hexagon = [(f64, f64); 6];
+
+/// Ring as `x y outer inner`, its centre and radii
+type Ring = [f64; 4];
+
+/// Distance of the control points of a quarter circle Bézier curve from its ends, as a
+/// fraction of the radius
+const KAPPA: f64 = 0.5523;
+
+impl VectorRenderer {
+    /// Render the symbol as an Encapsulated PostScript file, its bounding box covering the
+    /// quiet zone and the text
+    pub fn render_eps(&self, symbol: &Symbol) -> String {
+        let (width, height, rects) = self.layout(symbol);
+        let mut eps = String::new();
+        let _ = writeln!(eps, "%!PS-Adobe-3.0 EPSF-3.0");
+        let _ = writeln!(eps, "%%Creator: barcode-rs");
+        let _ = writeln!(eps, "%%BoundingBox: 0 0 {} {}", width.ceil(), height.ceil());
+        let _ = writeln!(
+            eps,
+            "%%HiResBoundingBox: 0 0 {} {}",
+            number(width),
+            number(height)
+        );
+        let _ = writeln!(eps, "%%EndComments");
+        let _ = writeln!(eps, "0 setgray");
+        for [x, y, w, h] in rects {
+            let _ = writeln!(
+                eps,
+                "{} {} {} {} rectfill",
+                number(x),
+                number(y),
+                number(w),
+                number(h)
+            );
+        }
+        if let Symbol::Hexagonal(maxicode) = symbol {
+            let (hexagons, rings) = self.hexagons(maxicode, height);
+            for hexagon in hexagons {
+                let [(x, y), rest @ ..] = hexagon.map(|(x, y)| (number(x), number(y)));
+                let _ = write!(eps, "{x} {y} moveto");
+                for (x, y) in rest {
+                    let _ = write!(eps, " {x} {y} lineto");
+                }
+                let _ = writeln!(eps, " closepath fill");
+            }
+            for [x, y, outer, inner] in rings {
+                let _ = write!(eps, "newpath");
+                for r in [outer, inner] {
+                    let _ = write!(
+                        eps,
+                        " {} {} moveto {} {} {} 0 360 arc closepath",
+                        number(x + r),
+                        number(y),
+                        number(x),
+                        number(y),
+                        number(r)
+                    );
+                }
+                let _ = writeln!(eps, " eofill");
+            }
+        }
+        if let Some(text) = &self.text {
+            let _ = writeln!(
+                eps,
+                "/Helvetica findfont {} scalefont setfont",
+                number(self.font_size)
+            );
+            let _ = writeln!(
+                eps,
+                "({}) dup stringwidth pop 2 div neg {} add {} moveto show",
+                escape(text),
+                number(width / 2.0),
+                number(self.text_baseline())
+            );
+        }
+        let _ = writeln!(eps, "showpage");
+        let _ = writeln!(eps, "%%EOF");
+        eps
+    }
+
+    /// Render the symbol as a single page PDF file of its size, the quiet zone and the text
+    /// included.
+    ///
+    /// The text is centered from the width of the Helvetica digits.
+    pub fn render_pdf(&self, symbol: &Symbol) -> Vec<u8> {
+        let (width, height, rects) = self.layout(symbol);
+        let mut content = String::from("0 g\n");
+        for [x, y, w, h] in rects {
+            let _ = writeln!(
+                content,
+                "{} {} {} {} re f",
+                number(x),
+                number(y),
+                number(w),
+                number(h)
+            );
+        }
+        if let Symbol::Hexagonal(maxicode) = symbol {
+            let (hexagons, rings) = self.hexagons(maxicode, height);
+            for hexagon in hexagons {
+                let [(x, y), rest @ ..] = hexagon.map(|(x, y)| (number(x), number(y)));
+                let _ = write!(content, "{x} {y} m");
+                for (x, y) in rest {
+                    let _ = write!(content, " {x} {y} l");
+                }
+                let _ = writeln!(content, " h f");
+            }
+            for [x, y, outer, inner] in rings {
+                for r in [outer, inner] {
+                    // four quarter circles counterclockwise from the right
+                    let k = KAPPA * r;
+                    let _ = write!(content, "{} {} m", number(x + r), number(y));
+                    for [(x1, y1), (x2, y2), (x3, y3)] in [
+                        [(x + r, y + k), (x + k, y + r), (x, y + r)],
+                        [(x - k, y + r), (x - r, y + k), (x - r, y)],
+                        [(x - r, y - k), (x - k, y - r), (x, y - r)],
+                        [(x + k, y - r), (x + r, y - k), (x + r, y)],
+                    ] {
+                        let _ = write!(
+                            content,
+                            " {} {} {} {} {} {} c",
+                            number(x1),
+                            number(y1),
+                            number(x2),
+                            number(y2),
+                            number(x3),
+                            number(y3)
+                        );
+                    }
+                    let _ = write!(content, " h ");
+                }
+                let _ = writeln!(content, "f*");
+            }
+        }
+        if let Some(text) = &self.text {
+            let text_width = text.chars().count() as f64 * HELVETICA_DIGIT_WIDTH * self.font_size;
+            let _ = writeln!(
+                content,
+                "BT /F1 {} Tf {} {} Td ({}) Tj ET",
+                number(self.font_size),
+                number((width - text_width) / 2.0),
+                number(self.text_baseline()),
+                escape(text)
+            );
+        }
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Contents 4 0 R \
+                 /Resources << /Font << /F1 5 0 R >> >> >>",
+                number(width),
+                number(height)
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{content}endstream",
+                content.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        ];
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            let _ = writeln!(pdf, "{} 0 obj\n{object}\nendobj", i + 1);
+        }
+        let xref = pdf.len();
+        let _ = writeln!(pdf, "xref\n0 {}", objects.len() + 1);
+        // entries are 20 bytes long, their end of line included
+        pdf.push_str("0000000000 65535 f \n");
+        for offset in offsets {
+            let _ = writeln!(pdf, "{offset:010} 00000 n ");
+        }
+        let _ = writeln!(
+            pdf,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF",
+            objects.len() + 1
+        );
+        pdf.into_bytes()
+    }
+
+    /// Height taken by the text between the symbol and the quiet zone, with a gap of a quarter
+    /// of its size
+    fn text_height(&self) -> f64 {
+        if self.text.is_some() {
+            1.25 * self.font_size
+        } else {
+            0.0
+        }
+    }
+
+    /// Baseline of the text, leaving room for descenders above the quiet zone
+    fn text_baseline(&self) -> f64 {
+        self.quiet_zone as f64 * self.module_width + 0.25 * self.font_size
+    }
+
+    /// Width and height of the page, and the rectangles of the dark modules, each run of dark
+    /// modules of a row taking a rectangle, none for MaxiCode symbols
+    fn layout(&self, symbol: &Symbol) -> (f64, f64, Vec<Rect>) {
+        let x_dim = self.module_width;
+        let rows = match symbol {
+            Symbol::Linear(_) => vec![(0, self.bar_height)],
+            Symbol::Stacked(stacked) => stacked
+                .iter()
+                .enumerate()
+                .map(|(y, row)| (y, row.height as f64 * x_dim))
+                .collect(),
+            Symbol::Hexagonal(_) => Vec::new(),
+            _ => (0..symbol.height()).map(|y| (y, x_dim)).collect(),
+        };
+        let margin = self.quiet_zone as f64 * x_dim;
+        let (symbol_width, symbol_height) = match symbol {
+            Symbol::Hexagonal(maxicode) => {
+                let (width, height) = maxicode.size();
+                (width * x_dim, height * x_dim)
+            }
+            _ => (
+                symbol.width() as f64 * x_dim,
+                rows.iter().map(|&(_, height)| height).sum::<f64>(),
+            ),
+        };
+        let width = symbol_width + 2.0 * margin;
+        let height = symbol_height + 2.0 * margin + self.text_height();
+
+        let mut rects = Vec::new();
+        let reduction = self.bar_width_reduction.dots(POINTS_PER_INCH);
+        let mut bottom = height - margin;
+        for (y, row_height) in rows {
+            bottom -= row_height;
+            let runs = runs((0..symbol.width()).map(|x| symbol.get(x, y)));
+            for (left, bar_width) in bars(&runs, x_dim, reduction) {
+                rects.push([margin + left, bottom, bar_width, row_height]);
+            }
+        }
+        (width, height, rects)
+    }
+
+    /// Hexagons of the dark modules and dark rings of the bullseye of a MaxiCode symbol, on a
+    /// page of the given height
+    fn hexagons(&self, maxicode: &MaxiCodeSymbol, height: f64) -> (Vec<Hexagon>, Vec<Ring>) {
+        let x_dim = self.module_width;
+        let margin = self.quiet_zone as f64 * x_dim;
+        let point = |(x, y): (f64, f64)| (margin + x * x_dim, height - margin - y * x_dim);
+        // vertices one module wide between the vertical sides, the first one pointing up
+        let radius = 1.0 / 3f64.sqrt();
+        let hexagons = maxicode
+            .coordinates()
+            .into_iter()
+            .map(|(x, y)| {
+                std::array::from_fn(|i| {
+                    let angle = (90.0 + 60.0 * i as f64).to_radians();
+                    point((x + radius * angle.cos(), y - radius * angle.sin()))
+                })
+            })
+            .collect();
+        let bullseye = maxicode.bullseye();
+        let (x, y) = point(bullseye.center);
+        let rings = bullseye
+            .radii
+            .chunks(2)
+            .map(|radii| [x, y, radii[0] * x_dim, radii[1] * x_dim])
+            .collect();
+        (hexagons, rings)
+    }
+}
+
+/// Number with at most 3 decimals, without trailing zeros
+fn number(value: f64) -> String {
+    let number = format!("{value:.3}");
+    let number = number.trim_end_matches('0').trim_end_matches('.');
+    match number {
+        "-0" => "0".to_string(),
+        _ => number.to_string(),
+    }
+}
+
+/// Text as the content of a PostScript or PDF string, characters past ASCII replaced by `?`
+fn escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '\\' | '(' | ')' => vec!['\\', c],
+            ' '..='~' => vec![c],
+            _ => vec!['?'],
+        })
+        .collect()
+}
@@ -0,0 +1,217 @@
+use bit_vec::BitVec;
+
+use crate::{
+    format::{DotCodeSymbol, HanXinSymbol, MaxiCodeSymbol},
+    Elements,
+};
+
+/// Encoded barcode, see [`encode`](crate::encode).
+///
+/// Every variant is addressed as a grid of modules through [`Symbol::width`], [`Symbol::height`]
+/// and [`Symbol::get`], `y` growing downwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Symbol {
+    /// Single row of modules, `true` for bars
+    Linear(BitVec),
+    /// Rows of modules of the same width, top row first
+    Stacked(Vec<StackedRow>),
+    /// Rectangular grid of modules
+    Matrix(Matrix),
+    /// Grid of hexagonal modules
+    Hexagonal(Box<MaxiCodeSymbol>),
+    /// Height-modulated bars, separated by spaces of one bar width
+    FourState(Vec<FourStateBar>),
+    /// Tall and short bars, separated by spaces of one bar width
+    TwoState(Vec<TwoStateBar>),
+}
+
+/// Row of a [`Symbol::Stacked`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackedRow {
+    /// Modules of the row, `true` for bars
+    pub modules: BitVec,
+    /// Height of the row, in module widths
+    pub height: usize,
+}
+
+/// Rectangular grid of modules, row-major
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    width: usize,
+    height: usize,
+    modules: Vec<bool>,
+}
+
+/// Bar of a [`Symbol::FourState`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FourStateBar {
+    /// Bar covering the ascender, tracker and descender
+    Full,
+    /// Bar covering the ascender and tracker
+    Ascender,
+    /// Bar covering the tracker and descender
+    Descender,
+    /// Bar covering the tracker only
+    Tracker,
+}
+
+/// Bar of a [`Symbol::TwoState`], both heights standing on the same baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwoStateBar {
+    /// Full height bar
+    Tall,
+    /// Half height bar
+    Short,
+}
+
+impl Symbol {
+    /// Number of module columns
+    pub fn width(&self) -> usize {
+        match self {
+            Symbol::Linear(modules) => modules.len(),
+            Symbol::Stacked(rows) => rows.iter().map(|r| r.modules.len()).max().unwrap_or(0),
+            Symbol::Matrix(matrix) => matrix.width(),
+            Symbol::Hexagonal(_) => MaxiCodeSymbol::COLUMNS,
+            Symbol::FourState(bars) => (2 * bars.len()).saturating_sub(1),
+            Symbol::TwoState(bars) => (2 * bars.len()).saturating_sub(1),
+        }
+    }
+
+    /// Number of module rows, the ascender, tracker and descender for 4-state bars and the upper
+    /// and lower halves for 2-state bars
+    pub fn height(&self) -> usize {
+        match self {
+            Symbol::Linear(_) => 1,
+            Symbol::Stacked(rows) => rows.len(),
+            Symbol::Matrix(matrix) => matrix.height(),
+            Symbol::Hexagonal(_) => MaxiCodeSymbol::ROWS,
+            Symbol::FourState(_) => 3,
+            Symbol::TwoState(_) => 2,
+        }
+    }
+
+    /// Whether the module at column `x` of row `y` is dark
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        match self {
+            Symbol::Linear(modules) => y == 0 && modules.get(x).unwrap_or(false),
+            Symbol::Stacked(rows) => rows.get(y).and_then(|r| r.modules.get(x)).unwrap_or(false),
+            Symbol::Matrix(matrix) => matrix.get(x, y),
+            Symbol::Hexagonal(symbol) => symbol.get(x, y),
+            Symbol::FourState(bars) => {
+                if x % 2 == 1 {
+                    return false;
+                }
+                matches!(
+                    (bars.get(x / 2), y),
+                    (Some(_), 1)
+                        | (Some(FourStateBar::Full | FourStateBar::Ascender), 0)
+                        | (Some(FourStateBar::Full | FourStateBar::Descender), 2)
+                )
+            }
+            Symbol::TwoState(bars) => {
+                x.is_multiple_of(2)
+                    && matches!(
+                        (bars.get(x / 2), y),
+                        (Some(_), 1) | (Some(TwoStateBar::Tall), 0)
+                    )
+            }
+        }
+    }
+
+    /// Modules as `(x, y, dark)`, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        let width = self.width();
+        (0..self.height()).flat_map(move |y| (0..width).map(move |x| (x, y, self.get(x, y))))
+    }
+
+    /// Run lengths of the modules of a [`Symbol::Linear`], or of a [`Symbol::Stacked`] made of
+    /// a single row, not grouped by character
+    pub fn elements(&self) -> Option<Elements> {
+        self.as_linear().map(Elements::from)
+    }
+
+    /// Modules of a [`Symbol::Linear`], or of a [`Symbol::Stacked`] made of a single row
+    pub fn as_linear(&self) -> Option<&BitVec> {
+        match self {
+            Symbol::Linear(modules) => Some(modules),
+            Symbol::Stacked(rows) if rows.len() == 1 => Some(&rows[0].modules),
+            _ => None,
+        }
+    }
+}
+
+impl Matrix {
+    /// Build a `width` by `height` grid from its modules, row-major
+    pub fn new(width: usize, height: usize, modules: Vec<bool>) -> Self {
+        assert_eq!(modules.len(), width * height);
+        Self {
+            width,
+            height,
+            modules,
+        }
+    }
+
+    /// Number of module columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of module rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the module at column `x` of row `y` is dark
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.modules[y * self.width + x]
+    }
+}
+
+impl From<BitVec> for Symbol {
+    fn from(modules: BitVec) -> Self {
+        Symbol::Linear(modules)
+    }
+}
+
+impl From<MaxiCodeSymbol> for Symbol {
+    fn from(symbol: MaxiCodeSymbol) -> Self {
+        Symbol::Hexagonal(Box::new(symbol))
+    }
+}
+
+impl From<DotCodeSymbol> for Symbol {
+    fn from(symbol: DotCodeSymbol) -> Self {
+        let (width, height) = (symbol.width(), symbol.height());
+        let modules = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| symbol.get(x, y))
+            .collect();
+        Symbol::Matrix(Matrix::new(width, height, modules))
+    }
+}
+
+impl From<HanXinSymbol> for Symbol {
+    fn from(symbol: HanXinSymbol) -> Self {
+        let size = symbol.size();
+        let modules = (0..size)
+            .flat_map(|y| (0..size).map(move |x| (x, y)))
+            .map(|(x, y)| symbol.get(x, y))
+            .collect();
+        Symbol::Matrix(Matrix::new(size, size, modules))
+    }
+}
+
+/// Linear and single row stacked symbols convert back to their modules, any other symbol is
+/// returned as the error
+impl TryFrom<Symbol> for BitVec {
+    type Error = Symbol;
+
+    fn try_from(symbol: Symbol) -> Result<Self, Self::Error> {
+        match symbol {
+            Symbol::Linear(modules) => Ok(modules),
+            Symbol::Stacked(mut rows) if rows.len() == 1 => Ok(rows.remove(0).modules),
+            _ => Err(symbol),
+        }
+    }
+}